@@ -0,0 +1,176 @@
+// Privileged helper: runs as root (via pkexec or sudo) and performs the few
+// process actions in helper_protocol.rs for one unprivileged user.
+//
+//     task-manager-helper --socket /run/user/1000/task-manager-helper.sock --allow-uid 1000 \
+//         --settings /home/user/.config/task-manager/settings.json
+
+// The GUI uses the client half of the protocol (to_line, validate), which this binary doesn't need.
+#[allow(dead_code)]
+#[path = "../utils/helper_protocol.rs"]
+mod helper_protocol;
+// The same protected list and read-only switch the GUI enforces, re-read for every request.
+#[allow(dead_code)]
+#[path = "../utils/protection.rs"]
+mod protection;
+#[allow(dead_code)]
+#[path = "../utils/settings.rs"]
+mod settings;
+
+use std::collections::HashMap;
+use std::io::{ BufRead, BufReader, Write };
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{ UnixListener, UnixStream };
+use std::path::PathBuf;
+
+use helper_protocol::{ peer_uid, process_cgroup, read_start_time, stat_field, Request, MANAGED_GROUP };
+use protection::{ Guard, Origin };
+use settings::Settings;
+
+// Exits after this long without connections, so a forgotten helper doesn't keep root around.
+const IDLE_TIMEOUT_MS: libc::c_int = 10 * 60 * 1000;
+// PF_KTHREAD in the flags field of /proc/<pid>/stat.
+const PF_KTHREAD: u64 = 0x00200000;
+
+struct Options {
+    socket: PathBuf,
+    allow_uid: u32,
+    settings: Option<PathBuf>,
+}
+
+// Where each process we moved into the managed subtree came from, keyed by (pid, start time):
+// moving a process back out is only allowed to that cgroup.
+type Origins = HashMap<(u32, u64), String>;
+
+fn parse_options() -> Result<Options, String> {
+    let mut socket = None;
+    let mut settings = None;
+    // pkexec and sudo tell us who asked for the elevation.
+    let mut allow_uid = ["PKEXEC_UID", "SUDO_UID"].iter().find_map(|name| std::env::var(name).ok()?.parse().ok());
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--socket" => socket = args.next().map(PathBuf::from),
+            "--settings" => settings = args.next().map(PathBuf::from),
+            "--allow-uid" => allow_uid = Some(args.next().and_then(|uid| uid.parse().ok()).ok_or("--allow-uid espera um número")?),
+            _ => return Err(format!("argumento desconhecido: {}", arg)),
+        }
+    }
+    Ok(Options {
+        socket: socket.ok_or("uso: task-manager-helper --socket <caminho> [--allow-uid <uid>] [--settings <caminho>]")?,
+        allow_uid: allow_uid.unwrap_or_else(|| unsafe { libc::getuid() }),
+        settings,
+    })
+}
+
+fn is_kernel_thread(pid: u32) -> bool {
+    stat_field(pid, 6).map(|flags| flags & PF_KTHREAD != 0).unwrap_or(false)
+}
+
+fn is_managed_cgroup(cgroup: &str) -> bool {
+    cgroup.trim_start_matches('/').split('/').next() == Some(MANAGED_GROUP)
+}
+
+// Whatever the user's settings say, some targets are never handed out by a root helper.
+fn check_request(request: &Request, options: &Options, origins: &Origins) -> Result<(), String> {
+    let pid = request.pid();
+    if pid == 1 || pid == std::process::id() {
+        return Err(format!("PID {} nunca é alterado pelo auxiliar", pid));
+    }
+    if is_kernel_thread(pid) {
+        return Err(format!("PID {} é uma thread do kernel", pid));
+    }
+    let settings = options.settings.as_ref().map(|path| Settings::load_from(path)).unwrap_or_default();
+    // We can't tell a confirmed click from any other process of the same user, so "confirm" counts as "block".
+    Guard::new(&settings).authorize(pid, Origin::Automatic)?;
+    if let Request::Cgroup { cgroup, .. } = request {
+        let restoring = read_start_time(pid).and_then(|start| origins.get(&(pid, start))).map(|origin| origin.trim_start_matches('/').eq(cgroup)).unwrap_or(false);
+        if !is_managed_cgroup(cgroup) && !restoring {
+            return Err(format!("só é permitido mover para {}/ ou de volta ao cgroup de origem", MANAGED_GROUP));
+        }
+    }
+    Ok(())
+}
+
+fn execute(request: &Request, options: &Options, origins: &mut Origins) -> Result<(), String> {
+    check_request(request, options, origins)?;
+    let previous = process_cgroup(request.pid());
+    request.execute().map_err(|err| err.to_string())?;
+    if let (Request::Cgroup { pid, cgroup }, Some(previous), Some(start)) = (request, previous, read_start_time(request.pid())) {
+        match is_managed_cgroup(cgroup) {
+            true if !is_managed_cgroup(&previous) => {
+                origins.insert((*pid, start), previous);
+            }
+            true => {}
+            false => {
+                origins.remove(&(*pid, start));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn serve(stream: UnixStream, options: &Options, origins: &mut Origins) {
+    // Connections are served one at a time, so an idle client mustn't hold the helper.
+    let _ = stream.set_read_timeout(Some(std::time::Duration::from_secs(30)));
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    match peer_uid(&stream) {
+        Some(uid) if uid == options.allow_uid || uid == 0 => {}
+        uid => {
+            eprintln!("conexão recusada do uid {:?}", uid);
+            let _ = writeln!(writer, "err usuário não autorizado");
+            return;
+        }
+    }
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let response = match Request::parse(&line).and_then(|request| execute(&request, options, origins)) {
+            Ok(()) => "ok".to_string(),
+            Err(err) => format!("err {}", err),
+        };
+        eprintln!("{} -> {}", line.trim(), response);
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
+
+fn wait_for_connection(listener: &UnixListener) -> bool {
+    let mut poll = libc::pollfd { fd: listener.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+    unsafe { libc::poll(&mut poll, 1, IDLE_TIMEOUT_MS) > 0 }
+}
+
+fn main() {
+    let options = match parse_options() {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+    let _ = std::fs::remove_file(&options.socket);
+    let listener = match UnixListener::bind(&options.socket) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("Erro ao abrir {}: {}", options.socket.display(), err);
+            std::process::exit(1);
+        }
+    };
+    // Only the allowed user may connect; the peer check in serve() is the second line of defence.
+    let _ = std::fs::set_permissions(&options.socket, std::fs::Permissions::from_mode(0o600));
+    let _ = std::os::unix::fs::chown(&options.socket, Some(options.allow_uid), None);
+
+    let mut origins = Origins::new();
+    while wait_for_connection(&listener) {
+        match listener.accept() {
+            Ok((stream, _)) => serve(stream, &options, &mut origins),
+            Err(err) => eprintln!("Erro ao aceitar conexão: {}", err),
+        }
+    }
+    let _ = std::fs::remove_file(&options.socket);
+}
\ No newline at end of file
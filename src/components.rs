@@ -0,0 +1,14 @@
+pub mod process_details;
+pub mod event_log;
+pub mod timeline;
+pub mod launcher;
+pub mod watchdog_panel;
+pub mod alerts;
+pub mod audit_log;
+pub mod protection_panel;
+pub mod cpu_breakdown;
+pub mod cpu_heatmap;
+pub mod cpu_info;
+pub mod sensors;
+pub mod power;
+pub mod energy;
\ No newline at end of file
@@ -0,0 +1,129 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    settings::Settings,
+    alerts::{ parse_alert_rules, AlertEvent, AlertKind, Notifier },
+    functions::format_timestamp,
+};
+
+// Keeps the alerts that are currently firing, shown as a banner above the tabs.
+#[derive(Clone)]
+pub struct BannerNotifier {
+    pub active: UseState<Vec<AlertEvent>>,
+}
+
+impl Notifier for BannerNotifier {
+    fn notify(&mut self, event: &AlertEvent) {
+        self.active.with_mut(|active| {
+            active.retain(|current| current.rule.ne(&event.rule));
+            if event.kind == AlertKind::Fired {
+                active.push(event.clone());
+            }
+        });
+    }
+}
+
+#[derive(Props)]
+pub struct AlertBannerProps<'a> {
+    active: &'a UseState<Vec<AlertEvent>>,
+}
+
+#[allow(non_snake_case)]
+pub fn AlertBanner<'a>(cx: Scope<'a, AlertBannerProps<'a>>) -> Element<'a> {
+    if cx.props.active.get().is_empty() {
+        return None;
+    }
+    render!(
+        div { class: "alert-banner",
+            for event in cx.props.active.get().iter() {
+                rsx!(
+                    div { key: "{event.rule}",
+                        format! { "{} - desde {}", event.message(), format_timestamp(event.timestamp) },
+                        button { class: "small-button",
+                            onclick: move |_| cx.props.active.with_mut(|active| active.retain(|current| current.rule.ne(&event.rule))),
+                            "Dispensar"
+                        },
+                    }
+                )
+            }
+        }
+    )
+}
+
+#[derive(Props)]
+pub struct AlertPanelProps<'a> {
+    settings: &'a UseState<Settings>,
+    history: &'a [AlertEvent],
+}
+
+#[allow(non_snake_case)]
+pub fn AlertPanel<'a>(cx: Scope<'a, AlertPanelProps<'a>>) -> Element<'a> {
+    let rules_text = use_state(cx, || cx.props.settings.get().alert_rules.join("\n"));
+    let message = use_state(cx, String::new);
+    let desktop = cx.props.settings.get().alert_desktop;
+    let (rules, errors) = parse_alert_rules(&rules_text.get().lines().map(|rule_line| rule_line.to_string()).collect::<Vec<_>>());
+
+    render!(
+        div { class: "details",
+            h4 { "Regras de alerta" },
+            div { "Uma regra por linha: <métrica> <op> <limite> [for <duração>] [hysteresis <margem>]" },
+            div { "Métricas: cpu, cpu:<núcleo>, mem, swap (GiB em uso), disk:<ponto de montagem> (GiB livres), net:<interface> (B/s), proc-cpu:<nome>, proc-mem:<nome> (MB)" },
+            textarea { class: "wide-input rules", value: "{rules_text}", oninput: move |evt| rules_text.set(evt.value.clone()) },
+            for err in errors.iter() {
+                rsx!(div { class: "message", "{err}" })
+            }
+            div {
+                label {
+                    input { r#type: "checkbox", checked: desktop,
+                        onclick: move |_| {
+                            let mut settings = cx.props.settings.get().clone();
+                            settings.alert_desktop = !settings.alert_desktop;
+                            if let Err(err) = settings.save() {
+                                message.set(format!("Erro ao salvar: {}", err));
+                            }
+                            cx.props.settings.set(settings);
+                        },
+                    },
+                    "Notificações da área de trabalho"
+                },
+                button {
+                    disabled: !errors.is_empty(),
+                    onclick: move |_| {
+                        let mut settings = cx.props.settings.get().clone();
+                        settings.alert_rules = rules_text.get().lines().filter(|rule_line| !rule_line.trim().is_empty()).map(|rule_line| rule_line.to_string()).collect();
+                        message.set(match settings.save() {
+                            Ok(()) => format!("{} regra(s) ativas", rules.len()),
+                            Err(err) => format!("Regras ativas, mas não salvas: {}", err),
+                        });
+                        cx.props.settings.set(settings);
+                    },
+                    "Salvar regras"
+                },
+                span { class: "message", " {message}" },
+            },
+        },
+        h4 { "Histórico" },
+        table {
+            thead {
+                tr {
+                    th { "Horário (UTC)" },
+                    th { "Situação" },
+                    th { "Regra" },
+                    th { "Valor" },
+                }
+            },
+            tbody {
+                for event in cx.props.history.iter().rev() {
+                    rsx!(
+                        tr {
+                            td { format_timestamp(event.timestamp) },
+                            td { match event.kind { AlertKind::Fired => "Disparado", AlertKind::Resolved => "Normalizado" } },
+                            td { "{event.rule}" },
+                            td { event.reading() },
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
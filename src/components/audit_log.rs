@@ -0,0 +1,52 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    audit::{ log_dir, read_audit_log },
+    functions::format_timestamp,
+};
+
+#[allow(non_snake_case)]
+pub fn AuditLog(cx: Scope) -> Element {
+    let entries = use_state(cx, read_audit_log);
+    let text_filter = use_state(cx, String::new);
+    let visible = entries.get().iter().rev().filter(|entry| entry.matches(text_filter.get().trim())).collect::<Vec<_>>();
+    let location = log_dir().display().to_string();
+
+    render!(
+        div { class: "toolbar",
+            input { placeholder: "Buscar por PID, nome, usuário, ação...", value: "{text_filter}", oninput: move |evt| text_filter.set(evt.value.clone()) },
+            button { onclick: move |_| entries.set(read_audit_log()), "Atualizar" },
+            format! { " {} de {} registros - gravados em {}", visible.len(), entries.get().len(), location },
+        },
+        table {
+            thead {
+                tr {
+                    th { "Horário (UTC)" },
+                    th { "Usuário" },
+                    th { "PID" },
+                    th { "Nome" },
+                    th { "Linha de comando" },
+                    th { "Ação" },
+                    th { "Parâmetros" },
+                    th { "Resultado" },
+                }
+            },
+            tbody {
+                for entry in visible {
+                    rsx!(
+                        tr {
+                            td { format_timestamp(entry.timestamp) },
+                            td { "{entry.user}" },
+                            td { "{entry.pid}" },
+                            td { "{entry.name}" },
+                            td { "{entry.cmdline}" },
+                            td { "{entry.action}" },
+                            td { "{entry.parameters}" },
+                            td { "{entry.outcome}" },
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
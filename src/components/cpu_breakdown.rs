@@ -0,0 +1,65 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::cpustat::{ CpuBreakdown, BREAKDOWN_FIELDS, CPU_HISTORY };
+
+const WIDTH: f64 = (CPU_HISTORY - 1) as f64;
+const HEIGHT: f64 = 100.0;
+
+#[derive(Props)]
+pub struct CpuBreakdownChartProps<'a> {
+    name: &'a str,
+    samples: &'a [CpuBreakdown],
+    height: u32,
+}
+
+#[allow(non_snake_case)]
+pub fn CpuBreakdownChart<'a>(cx: Scope<'a, CpuBreakdownChartProps<'a>>) -> Element<'a> {
+    let samples = cx.props.samples;
+    let latest = samples.last().copied().unwrap_or_default();
+    let areas = stacked_areas(samples);
+
+    render!(
+        div { class: "cpu-breakdown",
+            div { class: "cpu-breakdown-title",
+                "{cx.props.name} ",
+                for (field, colour) in BREAKDOWN_FIELDS.iter().filter(|(field, _)| latest.get(field) >= 0.1) {
+                    rsx!(
+                        span { class: "legend",
+                            span { class: "legend-colour", style: "background: {colour};" },
+                            format! { "{} {:.1}%", field, latest.get(field) }
+                        }
+                    )
+                }
+            },
+            if samples.len() < 2 {
+                rsx!("Coletando amostras...")
+            } else {
+                rsx!(
+                    svg { view_box: "0 0 {WIDTH} {HEIGHT}", preserve_aspect_ratio: "none", height: "{cx.props.height}px", width: "100%",
+                        for (field, colour, points) in areas {
+                            rsx!(polygon { key: "{field}", points: "{points}", fill: "{colour}" })
+                        }
+                    }
+                )
+            }
+        }
+    )
+}
+
+// Each field is drawn between the running total below it and the running total including it,
+// newest sample on the right edge like the other charts.
+fn stacked_areas(samples: &[CpuBreakdown]) -> Vec<(&'static str, &'static str, String)> {
+    let offset = CPU_HISTORY.saturating_sub(samples.len()) as f64;
+    let mut floor = vec![0.0f64; samples.len()];
+    BREAKDOWN_FIELDS
+        .iter()
+        .map(|(field, colour)| {
+            let top = samples.iter().zip(floor.iter()).map(|(sample, below)| below + sample.get(field) as f64).collect::<Vec<_>>();
+            let upper = top.iter().enumerate().map(|(idx, value)| format!("{:.2},{:.2}", offset + idx as f64, HEIGHT - value.min(HEIGHT)));
+            let lower = floor.iter().enumerate().rev().map(|(idx, value)| format!("{:.2},{:.2}", offset + idx as f64, HEIGHT - value.min(HEIGHT)));
+            let points = upper.chain(lower).collect::<Vec<_>>().join(" ");
+            floor = top;
+            (*field, *colour, points)
+        })
+        .collect()
+}
\ No newline at end of file
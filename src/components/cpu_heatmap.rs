@@ -0,0 +1,43 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    app_props::MyCpu,
+    cpustat::CPU_HISTORY,
+    functions::cpu_colour,
+};
+
+const ROW_HEIGHT: usize = 12;
+
+#[derive(Props)]
+pub struct CpuHeatmapProps<'a> {
+    cpus: &'a [MyCpu],
+}
+
+// One row per core, one column per sample, newest on the right.
+#[allow(non_snake_case)]
+pub fn CpuHeatmap<'a>(cx: Scope<'a, CpuHeatmapProps<'a>>) -> Element<'a> {
+    let height = cx.props.cpus.len().max(1) * ROW_HEIGHT;
+    let cells = cx.props.cpus.iter().enumerate().flat_map(|(row, cpu)| {
+        let offset = CPU_HISTORY.saturating_sub(cpu.uses.len());
+        cpu.uses.iter().enumerate().map(move |(column, usage)| (row * ROW_HEIGHT, offset + column, *usage, cpu.name.as_str()))
+    }).collect::<Vec<_>>();
+
+    render!(
+        div { class: "cpu-heatmap",
+            div { class: "cpu-heatmap-labels",
+                for cpu in cx.props.cpus.iter() {
+                    rsx!(div { key: "{cpu.name}", style: "height: {ROW_HEIGHT}px;", "{cpu.name}" })
+                }
+            },
+            svg { view_box: "0 0 {CPU_HISTORY} {height}", preserve_aspect_ratio: "none", height: "{height}px", width: "100%",
+                for (y, x, usage, name) in cells {
+                    rsx!(
+                        rect { x: "{x}", y: "{y}", width: "1", height: "{ROW_HEIGHT}", fill: "{cpu_colour(usage)}",
+                            title { format! { "{}: {:.1}%", name, usage } }
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
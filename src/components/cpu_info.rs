@@ -0,0 +1,88 @@
+use dioxus::{ html::*, prelude::* };
+use dioxus_charts::LineChart;
+
+use crate::utils::{
+    app_props::MyCpu,
+    cpuinfo::read_cpu_info,
+};
+
+#[derive(Props)]
+pub struct CpuInfoPanelProps<'a> {
+    cpus: &'a [MyCpu],
+}
+
+fn format_khz(khz: Option<u64>) -> String {
+    khz.map(|khz| format!("{}", khz / 1000)).unwrap_or_else(|| "-".to_string())
+}
+
+#[allow(non_snake_case)]
+pub fn CpuInfoPanel<'a>(cx: Scope<'a, CpuInfoPanelProps<'a>>) -> Element<'a> {
+    let info = use_state(cx, read_cpu_info);
+    let cpus = cx.props.cpus;
+    // Average and fastest core per sample: a drop in both while busy usually means throttling.
+    let samples = cpus.iter().map(|cpu| cpu.frequencies.len()).min().unwrap_or(0);
+    let at = |cpu: &MyCpu, idx: usize| cpu.frequencies[cpu.frequencies.len() - samples + idx] as f32;
+    let average = (0..samples).map(|idx| cpus.iter().map(|cpu| at(cpu, idx)).sum::<f32>() / cpus.len().max(1) as f32).collect::<Vec<_>>();
+    let fastest = (0..samples).map(|idx| cpus.iter().map(|cpu| at(cpu, idx)).fold(0.0, f32::max)).collect::<Vec<_>>();
+    let has_frequency = fastest.iter().any(|mhz| *mhz > 0.0);
+
+    render!(
+        div { class: "cpu-info",
+            h4 { "{info.model}" },
+            div { format! { "{} socket(s), {} núcleos físicos, {} threads, {} nó(s) NUMA", info.sockets(), info.physical_cores(), info.cores.len(), info.numa_nodes() } },
+            for cache in info.caches.iter() {
+                rsx!(div { "{cache}" })
+            }
+            button { onclick: move |_| info.set(read_cpu_info()), "Atualizar" },
+            if samples >= 2 && has_frequency {
+                rsx!(
+                    LineChart {
+                        width: "100%",
+                        height: "100%",
+                        padding_top: 30,
+                        padding_left: 50,
+                        padding_right: 90,
+                        padding_bottom: 30,
+                        show_grid_ticks: true,
+                        show_dotted_grid: false,
+                        series: vec![average, fastest],
+                        labels: vec!["60".to_string(), "0".to_string()],
+                        series_labels: vec!["Média (MHz)".to_string(), "Máxima (MHz)".to_string()],
+                    }
+                )
+            }
+            table {
+                thead {
+                    tr {
+                        th { "CPU" },
+                        th { "MHz" },
+                        th { "Mín." },
+                        th { "Máx." },
+                        th { "Governor" },
+                        th { "Socket" },
+                        th { "Núcleo" },
+                        th { "SMT" },
+                        th { "NUMA" },
+                    }
+                },
+                tbody {
+                    for core in info.cores.iter() {
+                        rsx!(
+                            tr { key: "{core.name}",
+                                td { "{core.name}" },
+                                td { cpus.iter().find(|cpu| cpu.name.eq(&core.name)).and_then(|cpu| cpu.frequencies.last()).map(|mhz| mhz.to_string()).unwrap_or_else(|| "-".to_string()) },
+                                td { format_khz(core.min_khz) },
+                                td { format_khz(core.max_khz) },
+                                td { "{core.governor}" },
+                                td { core.package.map(|package| package.to_string()).unwrap_or_else(|| "-".to_string()) },
+                                td { core.core.map(|id| id.to_string()).unwrap_or_else(|| "-".to_string()) },
+                                td { "{core.siblings}" },
+                                td { core.numa_node.map(|node| node.to_string()).unwrap_or_else(|| "-".to_string()) },
+                            }
+                        )
+                    }
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use dioxus::{ html::*, prelude::* };
+use dioxus_charts::LineChart;
+
+use crate::utils::{
+    rapl::{ process_shares, RaplPower, RaplSource },
+    settings::Settings,
+    tracking::ProcessTable,
+};
+
+const MAX_PROCESS_SHARES: usize = 20;
+
+#[derive(Props)]
+pub struct EnergyPanelProps<'a> {
+    energy: &'a [RaplPower],
+    processes: &'a ProcessTable,
+    settings: &'a UseState<Settings>,
+}
+
+#[allow(non_snake_case)]
+pub fn EnergyPanel<'a>(cx: Scope<'a, EnergyPanelProps<'a>>) -> Element<'a> {
+    let present = use_state(cx, || RaplSource::default().is_present());
+    let per_process = cx.props.settings.get().energy_per_process;
+    let energy = cx.props.energy;
+    let samples = energy.iter().map(|zone| zone.history.len()).min().unwrap_or(0);
+    let shares = match per_process {
+        true => process_shares(energy, &cx.props.processes.live()),
+        false => Vec::new(),
+    };
+
+    let toggle = move |_| {
+        let mut settings = cx.props.settings.get().clone();
+        settings.energy_per_process = !per_process;
+        if let Err(err) = settings.save() {
+            eprintln!("Erro ao salvar as configurações: {}", err);
+        }
+        cx.props.settings.set(settings);
+    };
+
+    render!(
+        if !*present.get() {
+            rsx!(div { class: "message", "Nenhum contador RAPL em /sys/class/powercap" })
+        } else if energy.is_empty() {
+            rsx!(div { class: "message", "Contadores RAPL sem permissão de leitura (energy_uj costuma exigir root)" })
+        }
+        if samples >= 2 {
+            rsx!(
+                LineChart {
+                    width: "100%",
+                    height: "100%",
+                    padding_top: 30,
+                    padding_left: 50,
+                    padding_right: 90,
+                    padding_bottom: 30,
+                    show_grid_ticks: true,
+                    show_dotted_grid: false,
+                    series: energy.iter().map(|zone| zone.history[zone.history.len() - samples..].iter().map(|watts| *watts as f32).collect::<Vec<_>>()).collect::<Vec<_>>(),
+                    labels: vec!["60".to_string(), "0".to_string()],
+                    series_labels: energy.iter().map(|zone| zone.label()).collect::<Vec<_>>(),
+                }
+            )
+        }
+        table {
+            thead {
+                tr {
+                    th { "Domínio" },
+                    th { "Potência (W)" },
+                }
+            },
+            tbody {
+                for zone in energy.iter() {
+                    rsx!(
+                        tr { key: "{zone.id}",
+                            td { zone.label() },
+                            td { format! { "{:.2}", zone.watts } },
+                        }
+                    )
+                }
+            }
+        }
+        div { class: "toolbar",
+            label {
+                input { r#type: "checkbox", checked: per_process, onclick: toggle },
+                "Estimar consumo por processo (proporcional ao tempo de CPU)"
+            },
+        },
+        if per_process {
+            rsx!(
+                table {
+                    thead {
+                        tr {
+                            th { "PID" },
+                            th { "Nome" },
+                            th { "Estimativa (W)" },
+                        }
+                    },
+                    tbody {
+                        for (pid, name, watts) in shares.into_iter().take(MAX_PROCESS_SHARES) {
+                            rsx!(
+                                tr { key: "{pid}",
+                                    td { "{pid}" },
+                                    td { "{name}" },
+                                    td { format! { "{:.2}", watts } },
+                                }
+                            )
+                        }
+                    }
+                }
+            )
+        }
+    )
+}
\ No newline at end of file
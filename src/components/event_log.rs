@@ -0,0 +1,78 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    events::{ EventKind, ProcessEvent, export_csv },
+    functions::{ format_timestamp, now_secs },
+};
+
+#[derive(Props)]
+pub struct EventLogProps<'a> {
+    events: &'a [ProcessEvent],
+}
+
+#[allow(non_snake_case)]
+pub fn EventLog<'a>(cx: Scope<'a, EventLogProps<'a>>) -> Element<'a> {
+    let text_filter = use_state(cx, String::new);
+    let kind = use_state(cx, || "Todos".to_string());
+    let message = use_state(cx, String::new);
+
+    let filtered = cx.props.events.iter()
+        .rev()
+        .filter(|event| event.matches(text_filter.get()))
+        .filter(|event| match kind.get().as_str() {
+            "Iniciados" => event.kind == EventKind::Started,
+            "Encerrados" => event.kind == EventKind::Exited,
+            _ => true,
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let to_export = filtered.clone();
+
+    render!(
+        div { class: "toolbar",
+            input { placeholder: "Nome ou PID", value: "{text_filter}", oninput: move |evt| text_filter.set(evt.value.clone()) },
+            select { onchange: move |evt| kind.set(evt.data.value.clone()),
+                for opt in ["Todos", "Iniciados", "Encerrados"] {
+                    rsx!(option { label: "{opt}", value: "{opt}", selected: kind.get().eq(opt) })
+                }
+            },
+            button {
+                onclick: move |_| {
+                    let export_path = std::env::current_dir().unwrap_or_default().join(format!("eventos-{}.csv", now_secs()));
+                    message.set(match export_csv(&to_export, &export_path) {
+                        Ok(()) => format!("Exportado para {}", export_path.display()),
+                        Err(err) => format!("Erro ao exportar: {}", err),
+                    });
+                },
+                "Exportar CSV"
+            },
+            span { class: "message", "{message}" },
+        },
+        table {
+            thead {
+                tr {
+                    th { "Horário (UTC)" },
+                    th { "Evento" },
+                    th { "PID" },
+                    th { "Nome" },
+                    th { "Pai" },
+                    th { "Duração" },
+                }
+            },
+            tbody {
+                for event in filtered {
+                    rsx!(
+                        tr {
+                            td { format_timestamp(event.timestamp) },
+                            td { event.kind.label() },
+                            td { "{event.pid}" },
+                            td { "{event.name}" },
+                            td { event.parent.map(|pid| pid.to_string()).unwrap_or_default() },
+                            td { event.lifetime.map(|secs| format!("{} s", secs)).unwrap_or_default() },
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
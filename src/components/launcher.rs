@@ -0,0 +1,107 @@
+use dioxus::{ html::*, prelude::* };
+use futures_channel::mpsc::UnboundedSender;
+
+use crate::utils::{
+    launcher::{ launch, LaunchRequest, LaunchedTask, LaunchUpdate },
+    functions::format_timestamp,
+};
+
+#[derive(Props)]
+pub struct LaunchDialogProps<'a> {
+    tasks: &'a UseState<Vec<LaunchedTask>>,
+    updates: &'a UnboundedSender<LaunchUpdate>,
+    on_close: EventHandler<'a, ()>,
+}
+
+#[allow(non_snake_case)]
+pub fn LaunchDialog<'a>(cx: Scope<'a, LaunchDialogProps<'a>>) -> Element<'a> {
+    let command = use_state(cx, String::new);
+    let cwd = use_state(cx, || std::env::var("HOME").unwrap_or_default());
+    let env = use_state(cx, String::new);
+    let nice = use_state(cx, || "0".to_string());
+    let cpu_percent = use_state(cx, String::new);
+    let memory_mib = use_state(cx, String::new);
+    let message = use_state(cx, String::new);
+
+    render!(
+        div { class: "dialog",
+            h3 { "Novo processo" },
+            div { input { class: "wide-input", placeholder: "Comando (executado com sh -c)", value: "{command}", oninput: move |evt| command.set(evt.value.clone()) } },
+            div { input { class: "wide-input", placeholder: "Diretório de trabalho", value: "{cwd}", oninput: move |evt| cwd.set(evt.value.clone()) } },
+            div { textarea { class: "wide-input", placeholder: "Variáveis de ambiente (CHAVE=valor, uma por linha)", value: "{env}", oninput: move |evt| env.set(evt.value.clone()) } },
+            div {
+                "Nice: ",
+                input { class: "small-input", value: "{nice}", oninput: move |evt| nice.set(evt.value.clone()) },
+                " CPU máx. (%): ",
+                input { class: "small-input", value: "{cpu_percent}", oninput: move |evt| cpu_percent.set(evt.value.clone()) },
+                " Memória máx. (MiB): ",
+                input { class: "small-input", value: "{memory_mib}", oninput: move |evt| memory_mib.set(evt.value.clone()) },
+            },
+            div {
+                button {
+                    onclick: move |_| {
+                        let request = LaunchRequest {
+                            command: command.get().clone(),
+                            cwd: cwd.get().clone(),
+                            env: env.get().clone(),
+                            nice: nice.get().clone(),
+                            cpu_percent: cpu_percent.get().clone(),
+                            memory_mib: memory_mib.get().clone(),
+                        };
+                        let id = cx.props.tasks.get().len() + 1;
+                        match launch(id, &request, cx.props.updates.clone()) {
+                            Ok(task) => {
+                                message.set(format!("Iniciado com PID {}", task.pid));
+                                cx.props.tasks.with_mut(|tasks| tasks.push(task));
+                            }
+                            Err(err) => message.set(err),
+                        }
+                    },
+                    "Executar"
+                },
+                button { onclick: move |_| cx.props.on_close.call(()), "Fechar" },
+                span { class: "message", " {message}" },
+            },
+        }
+    )
+}
+
+#[derive(Props)]
+pub struct TaskLogsProps<'a> {
+    tasks: &'a [LaunchedTask],
+}
+
+#[allow(non_snake_case)]
+pub fn TaskLogs<'a>(cx: Scope<'a, TaskLogsProps<'a>>) -> Element<'a> {
+    let selected = use_state(cx, || None::<usize>);
+    let task = selected.get()
+        .and_then(|id| cx.props.tasks.iter().find(|task| task.id == id))
+        .or(cx.props.tasks.last());
+
+    render!(
+        if cx.props.tasks.is_empty() {
+            rsx!(div { class: "toolbar", "Nenhum processo iniciado pelo gerenciador" })
+        }
+        div { class: "toolbar",
+            for task in cx.props.tasks.iter() {
+                rsx!(
+                    button { onclick: move |_| selected.set(Some(task.id)),
+                        format! { "#{} {} ({})", task.id, task.command, if task.is_running() { "executando" } else { "encerrado" } }
+                    }
+                )
+            }
+        },
+        if let Some(task) = task {
+            rsx!(
+                div { class: "details",
+                    div { format! { "PID {} - iniciado em {} (UTC)", task.pid, format_timestamp(task.started) } },
+                    if let Some(cgroup) = &task.cgroup {
+                        rsx!(div { format! { "Cgroup: {}", cgroup.display() } })
+                    }
+                    div { format! { "Status: {}", task.exit_status.clone().unwrap_or_else(|| "executando".to_string()) } },
+                    pre { class: "task-log", task.output.join("\n") },
+                }
+            )
+        }
+    )
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use dioxus::{ html::*, prelude::* };
+use dioxus_charts::LineChart;
+
+use crate::utils::power::{ format_duration, PowerStatus };
+
+#[derive(Props)]
+pub struct PowerPanelProps<'a> {
+    power: &'a PowerStatus,
+    cpu_load: Vec<f32>,
+}
+
+fn format_value(value: Option<f64>, unit: &str) -> String {
+    value.map(|value| format!("{:.1} {}", value, unit)).unwrap_or_else(|| "-".to_string())
+}
+
+// Both series trimmed to the newest samples they have in common.
+fn aligned_series(watts: &[f64], cpu_load: &[f32]) -> Vec<Vec<f32>> {
+    let samples = watts.len().min(cpu_load.len());
+    vec![
+        watts[watts.len() - samples..].iter().map(|watts| *watts as f32).collect(),
+        cpu_load[cpu_load.len() - samples..].to_vec(),
+    ]
+}
+
+#[allow(non_snake_case)]
+pub fn PowerPanel<'a>(cx: Scope<'a, PowerPanelProps<'a>>) -> Element<'a> {
+    let power = cx.props.power;
+    let ac = match power.ac_online {
+        Some(true) => "Na tomada",
+        Some(false) => "Na bateria",
+        None => "Fonte desconhecida",
+    };
+
+    render!(
+        div { class: "power",
+            div { "{ac}" },
+            for battery in power.batteries.iter() {
+                rsx!(
+                    div { key: "{battery.name}",
+                        h4 { format! { "{}: {:.0}% - {}", battery.name, battery.capacity.unwrap_or(0.0), battery.status_label() } },
+                        div { format! { "Consumo: {} - Energia: {} de {}", format_value(battery.power_w, "W"), format_value(battery.energy_wh, "Wh"), format_value(battery.energy_full_wh, "Wh") } },
+                        div { format! { "Saúde: {} - Ciclos: {}", format_value(battery.health(), "%"), battery.cycle_count.map(|cycles| cycles.to_string()).unwrap_or_else(|| "-".to_string()) } },
+                        div {
+                            match (battery.remaining_secs, battery.status.as_str()) {
+                                (Some(secs), "Charging") => format!("Carga completa em ~{}", format_duration(secs)),
+                                (Some(secs), _) => format!("Restante: ~{}", format_duration(secs)),
+                                (None, _) => "Restante: -".to_string(),
+                            }
+                        },
+                        // Drain next to CPU load, over the same window, to see which bursts cost battery.
+                        if battery.power_history.len() >= 2 && cx.props.cpu_load.len() >= 2 {
+                            rsx!(
+                                LineChart {
+                                    width: "100%",
+                                    height: "100%",
+                                    padding_top: 30,
+                                    padding_left: 50,
+                                    padding_right: 90,
+                                    padding_bottom: 30,
+                                    show_grid_ticks: true,
+                                    show_dotted_grid: false,
+                                    series: aligned_series(&battery.power_history, &cx.props.cpu_load),
+                                    labels: vec!["60".to_string(), "0".to_string()],
+                                    series_labels: vec!["W".to_string(), "CPU %".to_string()],
+                                }
+                            )
+                        }
+                    }
+                )
+            }
+        }
+    )
+}
\ No newline at end of file
@@ -0,0 +1,465 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    app_props::MyProcess,
+    cgroup::*,
+    limits::{ read_limits, set_limit },
+    oom::set_oom_score_adj,
+    state::state_label,
+    signals::{ send_signal, signal_name, SIGNALS },
+    kill_tree::{ kill_tree, Escalation },
+    restart::{ restart, LaunchContext },
+    audit::{ audited, AuditTarget },
+    helper::start_helper,
+    protection::{ Guard, Origin, Verdict },
+    priority::{ read_affinity, read_nice, renice, set_affinity, set_ionice, IONICE_CLASSES },
+};
+use futures_channel::oneshot;
+
+#[derive(Props, PartialEq)]
+pub struct ProcessDetailsProps {
+    process: MyProcess,
+    descendants: Vec<MyProcess>,
+    #[props(!optional)]
+    parent: Option<MyProcess>,
+    guard: Guard,
+}
+
+#[allow(non_snake_case)]
+pub fn ProcessDetails(cx: Scope<ProcessDetailsProps>) -> Element {
+    let process = &cx.props.process;
+    let cpu_percent = use_state(cx, String::new);
+    let memory_max = use_state(cx, String::new);
+    let memory_high = use_state(cx, String::new);
+    let io_max = use_state(cx, String::new);
+    let message = use_state(cx, String::new);
+    let limit_name = use_state(cx, || "Max open files".to_string());
+    let limit_soft = use_state(cx, String::new);
+    let limit_hard = use_state(cx, String::new);
+    let oom_adj = use_state(cx, || cx.props.process.oom_score_adj.to_string());
+    let confirm_kill_tree = use_state(cx, || false);
+    let first_signal = use_state(cx, || Escalation::default().first_signal);
+    let grace_secs = use_state(cx, || Escalation::default().grace.as_secs().to_string());
+    let escalate = use_state(cx, || true);
+    let acknowledged = use_state(cx, || false);
+    let nice = use_state(cx, || read_nice(cx.props.process.pid).map(|nice| nice.to_string()).unwrap_or_default());
+    let io_class = use_state(cx, || 2);
+    let io_level = use_state(cx, || "4".to_string());
+    let affinity = use_state(cx, || read_affinity(cx.props.process.pid).unwrap_or_default());
+    let elevating = use_state(cx, || false);
+
+    let pid = process.pid;
+    let cgroup = process.cgroup.clone();
+    let descendants = cx.props.descendants.iter().map(|proc| proc.pid).collect::<Vec<_>>();
+    // Bottom-up: the descendants come already ordered leaves first.
+    let mut tree_pids = descendants.clone();
+    tree_pids.push(pid);
+    let dir = cgroup_dir(&cgroup);
+    let limits = CgroupLimits::read(&dir);
+    let stats = CgroupStats::read(&dir);
+    let (limits_table, limits_error) = match read_limits(pid) {
+        Ok(limits_table) => (limits_table, None),
+        Err(err) => (Vec::new(), Some(format!("Não foi possível ler os limites: {}", err))),
+    };
+    let launch_context = LaunchContext::capture(pid).ok();
+    let limit_names = limits_table.iter().map(|limit| limit.name.clone()).collect::<Vec<_>>();
+    let verdict = cx.props.guard.check(pid);
+    let tree_verdict = cx.props.guard.check_all(&tree_pids);
+    let parent_verdict = cx.props.parent.as_ref().map(|parent| cx.props.guard.check(parent.pid)).unwrap_or(Verdict::Allowed);
+    let locked = is_locked(&verdict, *acknowledged.get());
+    let tree_locked = is_locked(&tree_verdict, *acknowledged.get());
+    let parent_locked = is_locked(&parent_verdict, *acknowledged.get());
+    let notices = [&verdict, &tree_verdict, &parent_verdict].into_iter()
+        .filter_map(|verdict| match verdict {
+            Verdict::Allowed => None,
+            Verdict::ReadOnly => Some("Modo somente leitura: todas as ações estão desativadas".to_string()),
+            Verdict::Blocked(reason) => Some(format!("Ações bloqueadas: {}", reason)),
+            Verdict::NeedsConfirmation(reason) => Some(format!("Requer confirmação: {}", reason)),
+        })
+        .fold(Vec::new(), |mut notices, notice| {
+            if !notices.contains(&notice) {
+                notices.push(notice);
+            }
+            notices
+        });
+    let origin = Origin::User { confirmed: *acknowledged.get() };
+    let needs_confirmation = [&verdict, &tree_verdict, &parent_verdict].iter().any(|verdict| matches!(verdict, Verdict::NeedsConfirmation(_)));
+
+    render!(
+        div { class: "details",
+            h3 { "{process.name} ({pid})" },
+            if unsafe { libc::geteuid() } != 0 {
+                rsx!(
+                    button { class: "small-button", disabled: *elevating.get(),
+                        onclick: move |_| {
+                            let (sender, receiver) = oneshot::channel();
+                            std::thread::spawn(move || {
+                                let _ = sender.send(start_helper());
+                            });
+                            let message = message.clone();
+                            let elevating = elevating.clone();
+                            elevating.set(true);
+                            message.set("Aguardando a autenticação...".to_string());
+                            cx.spawn(async move {
+                                if let Ok(result) = receiver.await {
+                                    message.set(match result {
+                                        Ok(()) => "Permissão de administrador obtida; repita a ação".to_string(),
+                                        Err(err) => err,
+                                    });
+                                }
+                                elevating.set(false);
+                            });
+                        },
+                        "Obter permissão de administrador"
+                    }
+                )
+            }
+            if !notices.is_empty() {
+                rsx!(
+                    div { class: "state-warning",
+                        for notice in notices {
+                            rsx!(div { notice })
+                        }
+                        if needs_confirmation {
+                            rsx!(
+                                label {
+                                    input { r#type: "checkbox", checked: *acknowledged.get(), onclick: move |_| acknowledged.set(!acknowledged.get()) },
+                                    "Confirmo que quero agir sobre um processo protegido"
+                                }
+                            )
+                        }
+                    }
+                )
+            }
+            div { format! { "Estado: {} ({})", process.state, state_label(process.state) } },
+            if process.state == 'D' {
+                rsx!(div { "Bloqueado em: {process.wchan}" })
+            }
+            if process.state == 'Z' {
+                rsx!(
+                    div { class: "state-warning",
+                        match &cx.props.parent {
+                            Some(parent) => {
+                                let ppid = parent.pid;
+                                rsx!(
+                                    format! { "Zumbi não coletado pelo pai {} ({}) ", parent.name, ppid },
+                                    button { disabled: parent_locked,
+                                        onclick: move |_| message.set(describe_signal(audited(ppid, "sinal", "SIGCHLD", || send_signal(ppid, libc::SIGCHLD, origin)), "SIGCHLD enviado ao pai")),
+                                        "Enviar SIGCHLD ao pai"
+                                    },
+                                    button { disabled: parent_locked,
+                                        onclick: move |_| message.set(describe_signal(audited(ppid, "sinal", "SIGTERM", || send_signal(ppid, libc::SIGTERM, origin)), "SIGTERM enviado ao pai")),
+                                        "Encerrar pai (SIGTERM)"
+                                    },
+                                )
+                            }
+                            None => rsx!("Zumbi sem pai conhecido"),
+                        }
+                    }
+                )
+            }
+            div { "Cgroup: {cgroup}",
+                if process.frozen {
+                    rsx!(span { class: "badge frozen", "congelado" })
+                }
+            },
+            div { class: "details-columns",
+                div {
+                    h4 { "Limites do cgroup" },
+                    div { "cpu.max: {limits.cpu_max}" },
+                    div { "memory.max: {limits.memory_max}" },
+                    div { "memory.high: {limits.memory_high}" },
+                    div { "io.max: {limits.io_max}" },
+                },
+                div {
+                    h4 { "Estrangulamento" },
+                    div { "Períodos: {stats.nr_periods} - Estrangulados: {stats.nr_throttled}" },
+                    div { format! { "Tempo estrangulado: {:.02} s", stats.throttled_usec as f64 / 1000000.0 } },
+                    div { format! { "Memória atual: {} MiB", stats.memory_current / 1024 / 1024 } },
+                    div { "Eventos memory.high: {stats.memory_high_events} - memory.max: {stats.memory_max_events} - OOM kills: {stats.oom_kills}" },
+                },
+            },
+            div {
+                h4 { "Limitar recursos (cgroup)" },
+                input { placeholder: "CPU (%)", value: "{cpu_percent}", oninput: move |evt| cpu_percent.set(evt.value.clone()) },
+                input { placeholder: "memory.max (MiB)", value: "{memory_max}", oninput: move |evt| memory_max.set(evt.value.clone()) },
+                input { placeholder: "memory.high (MiB)", value: "{memory_high}", oninput: move |evt| memory_high.set(evt.value.clone()) },
+                input { placeholder: "io.max (ex: 8:0 rbps=1048576)", value: "{io_max}", oninput: move |evt| io_max.set(evt.value.clone()) },
+                button { disabled: locked,
+                    onclick: move |_| {
+                        let parameters = format!("cpu={}% memory.max={} MiB memory.high={} MiB io.max={}", cpu_percent.get(), memory_max.get(), memory_high.get(), io_max.get());
+                        let result = audited(pid, "limites de cgroup", &parameters, || apply_limits(pid, cpu_percent.get(), memory_max.get(), memory_high.get(), io_max.get(), origin));
+                        message.set(match result {
+                            Ok(dir) => format!("Limites aplicados em {}", dir),
+                            Err(err) => err,
+                        });
+                    },
+                    "Aplicar limites"
+                },
+            },
+            div {
+                h4 { "Congelar" },
+                button { disabled: locked,
+                    onclick: move |_| message.set(describe(audited(pid, "congelar processo", "", || freeze_processes(pid, &[], origin)), "Processo congelado")),
+                    "Congelar processo"
+                },
+                button { disabled: tree_locked,
+                    onclick: move |_| message.set(describe(audited(pid, "congelar subárvore", &format!("filhos: {:?}", descendants), || freeze_processes(pid, &descendants, origin)), "Subárvore congelada")),
+                    format! { "Congelar subárvore ({} filhos)", cx.props.descendants.len() }
+                },
+                button { disabled: locked,
+                    onclick: move |_| message.set(describe(audited(pid, "congelar cgroup", &process.cgroup, || set_process_cgroup_frozen(pid, true, origin)), "Cgroup congelado")),
+                    "Congelar cgroup"
+                },
+                button { disabled: locked,
+                    onclick: move |_| message.set(describe(audited(pid, "descongelar cgroup", &process.cgroup, || set_process_cgroup_frozen(pid, false, origin)), "Cgroup descongelado")),
+                    "Descongelar"
+                },
+            },
+            div {
+                h4 { "Prioridade" },
+                div { format! { "Nice atual: {} - CPUs permitidas: {}", read_nice(pid).map(|nice| nice.to_string()).unwrap_or_else(|| "-".to_string()), read_affinity(pid).unwrap_or_else(|| "-".to_string()) } },
+                div {
+                    "Nice: ",
+                    input { class: "small-input", value: "{nice}", oninput: move |evt| nice.set(evt.value.clone()) },
+                    button { disabled: locked,
+                        onclick: move |_| {
+                            let result = nice.get().trim().parse::<i32>()
+                                .map_err(|_| format!("Nice inválido: {}", nice.get()))
+                                .and_then(|value| audited(pid, "renice", nice.get(), || renice(pid, value, origin)));
+                            message.set(describe_signal(result, "Prioridade alterada"));
+                        },
+                        "Alterar"
+                    },
+                },
+                div {
+                    "E/S: ",
+                    select { onchange: move |evt| io_class.set(evt.data.value.parse().unwrap_or(2)),
+                        for (name, class) in IONICE_CLASSES {
+                            rsx!(option { label: "{name}", value: "{class}", selected: *io_class.get() == class })
+                        }
+                    },
+                    " nível (0 a 7): ",
+                    input { class: "small-input", value: "{io_level}", oninput: move |evt| io_level.set(evt.value.clone()) },
+                    button { disabled: locked,
+                        onclick: move |_| {
+                            let parameters = format!("classe {} nível {}", io_class.get(), io_level.get());
+                            message.set(describe_signal(audited(pid, "ionice", &parameters, || set_ionice(pid, *io_class.get(), io_level.get(), origin)), "Prioridade de E/S alterada"));
+                        },
+                        "Alterar"
+                    },
+                },
+                div {
+                    "Afinidade (ex: 0,2-3): ",
+                    input { value: "{affinity}", oninput: move |evt| affinity.set(evt.value.clone()) },
+                    button { disabled: locked,
+                        onclick: move |_| message.set(describe_signal(audited(pid, "afinidade", affinity.get(), || set_affinity(pid, affinity.get(), origin)), "Afinidade alterada")),
+                        "Alterar"
+                    },
+                },
+            },
+            div {
+                h4 { "OOM" },
+                div { "oom_score: {process.oom_score} - oom_score_adj: {process.oom_score_adj}" },
+                input { placeholder: "oom_score_adj (-1000 a 1000)", value: "{oom_adj}", oninput: move |evt| oom_adj.set(evt.value.clone()) },
+                button { disabled: locked,
+                    onclick: move |_| message.set(describe_oom(audited(pid, "oom_score_adj", oom_adj.get(), || set_oom_score_adj(pid, oom_adj.get(), origin)))),
+                    "Alterar"
+                },
+                button { disabled: locked,
+                    onclick: move |_| message.set(describe_oom(audited(pid, "oom_score_adj", "-1000", || set_oom_score_adj(pid, "-1000", origin)))),
+                    "Proteger"
+                },
+                button { disabled: locked,
+                    onclick: move |_| message.set(describe_oom(audited(pid, "oom_score_adj", "1000", || set_oom_score_adj(pid, "1000", origin)))),
+                    "Sacrificar primeiro"
+                },
+            },
+            div {
+                h4 { "Limites do processo" },
+                if let Some(err) = limits_error {
+                    rsx!(div { class: "message", err })
+                }
+                table {
+                    thead {
+                        tr {
+                            th { "Limite" },
+                            th { "Uso atual" },
+                            th { "Soft" },
+                            th { "Hard" },
+                            th { "Unidade" },
+                        }
+                    },
+                    tbody {
+                        for limit in limits_table {
+                            rsx!(
+                                tr {
+                                    td { "{limit.name}" },
+                                    td { limit.usage.unwrap_or_else(|| "-".to_string()) },
+                                    td { "{limit.soft}" },
+                                    td { "{limit.hard}" },
+                                    td { "{limit.units}" },
+                                }
+                            )
+                        }
+                    }
+                },
+                select { onchange: move |evt| limit_name.set(evt.data.value.clone()),
+                    for name in limit_names {
+                        rsx!(
+                            option { label: "{name}", value: "{name}", selected: limit_name.get().eq(&name) }
+                        )
+                    }
+                },
+                input { placeholder: "Soft (unlimited; vazio mantém)", value: "{limit_soft}", oninput: move |evt| limit_soft.set(evt.value.clone()) },
+                input { placeholder: "Hard (unlimited; vazio mantém)", value: "{limit_hard}", oninput: move |evt| limit_hard.set(evt.value.clone()) },
+                button { disabled: locked,
+                    onclick: move |_| {
+                        let parameters = format!("{} soft={} hard={}", limit_name.get(), limit_soft.get(), limit_hard.get());
+                        message.set(match audited(pid, "prlimit", &parameters, || set_limit(pid, limit_name.get(), limit_soft.get(), limit_hard.get(), origin)) {
+                            Ok(()) => format!("{} alterado", limit_name.get()),
+                            Err(err) => err,
+                        });
+                    },
+                    "Alterar limite"
+                },
+            },
+            div {
+                h4 { "Reiniciar" },
+                match launch_context {
+                    Some(context) => rsx!(
+                        div { format! { "Comando: {}", context.command_line() } },
+                        div { format! { "Diretório: {} - UID {} - {} variáveis de ambiente", context.cwd.display(), context.uid, context.environ.len() } },
+                        button { disabled: locked,
+                            onclick: move |_| {
+                                let (sender, receiver) = oneshot::channel();
+                                std::thread::spawn(move || {
+                                    let _ = sender.send(audited(pid, "reiniciar", "", || restart(pid, origin)));
+                                });
+                                let message = message.clone();
+                                message.set("Reiniciando...".to_string());
+                                cx.spawn(async move {
+                                    if let Ok(result) = receiver.await {
+                                        message.set(result.unwrap_or_else(|err| err));
+                                    }
+                                });
+                            },
+                            "Reiniciar processo"
+                        },
+                    ),
+                    None => rsx!(div { "Não foi possível ler a linha de comando, diretório e ambiente deste processo" }),
+                }
+            },
+            div {
+                h4 { "Encerrar árvore de processos" },
+                if *confirm_kill_tree.get() {
+                    rsx!(
+                        div { class: "state-warning",
+                            format! { "Serão encerrados {} processos, de baixo para cima:", tree_pids.len() },
+                            ul { class: "kill-list",
+                                for proc in cx.props.descendants.iter() {
+                                    rsx!(li { "{proc.name} ({proc.pid})" })
+                                }
+                                li { "{process.name} ({pid})" },
+                            },
+                            "Sinal: ",
+                            select { onchange: move |evt| first_signal.set(evt.data.value.parse().unwrap_or(libc::SIGTERM)),
+                                for (name, number) in SIGNALS.iter().filter(|(_, number)| [libc::SIGTERM, libc::SIGINT, libc::SIGHUP].contains(number)) {
+                                    rsx!(option { label: "{name}", value: "{number}", selected: *first_signal.get() == *number })
+                                }
+                            },
+                            " Aguardar ",
+                            input { class: "small-input", value: "{grace_secs}", oninput: move |evt| grace_secs.set(evt.value.clone()) },
+                            " s ",
+                            label {
+                                input { r#type: "checkbox", checked: *escalate.get(), onclick: move |_| escalate.set(!escalate.get()) },
+                                "e depois SIGKILL"
+                            },
+                            div {
+                                button { disabled: tree_locked,
+                                    onclick: move |_| {
+                                        let escalation = Escalation {
+                                            first_signal: *first_signal.get(),
+                                            grace: std::time::Duration::from_secs(grace_secs.get().parse().unwrap_or(5)),
+                                            final_signal: if *escalate.get() { Some(libc::SIGKILL) } else { None },
+                                        };
+                                        let pids = tree_pids.clone();
+                                        let target = AuditTarget::capture(pid);
+                                        let (sender, receiver) = oneshot::channel();
+                                        std::thread::spawn(move || {
+                                            let report = kill_tree(&pids, escalation, origin);
+                                            let parameters = format!("PIDs {:?}, {} e {} s de espera{}",
+                                                pids,
+                                                signal_name(escalation.first_signal),
+                                                escalation.grace.as_secs(),
+                                                escalation.final_signal.map(|signal| format!(", depois {}", signal_name(signal))).unwrap_or_default(),
+                                            );
+                                            target.record("encerrar árvore", &parameters, &report);
+                                            let _ = sender.send(report);
+                                        });
+                                        let message = message.clone();
+                                        message.set("Encerrando árvore...".to_string());
+                                        cx.spawn(async move {
+                                            if let Ok(report) = receiver.await {
+                                                message.set(report);
+                                            }
+                                        });
+                                        confirm_kill_tree.set(false);
+                                    },
+                                    "Confirmar"
+                                },
+                                button { onclick: move |_| confirm_kill_tree.set(false), "Cancelar" },
+                            }
+                        }
+                    )
+                } else {
+                    rsx!(
+                        button { disabled: tree_locked, onclick: move |_| confirm_kill_tree.set(true), format! { "Encerrar árvore ({} processos)", cx.props.descendants.len() + 1 } }
+                    )
+                }
+            },
+            div { class: "message", "{message}" },
+        }
+    )
+}
+
+fn apply_limits(pid: u32, cpu_percent: &str, memory_max: &str, memory_high: &str, io_max: &str, origin: Origin) -> Result<String, String> {
+    let limits = CgroupLimits {
+        cpu_max: cpu_max_from_percent(cpu_percent)?,
+        memory_max: bytes_from_mib(memory_max)?,
+        memory_high: bytes_from_mib(memory_high)?,
+        io_max: io_max.to_string(),
+    };
+    let dir = move_to_managed_group(pid, origin).map_err(|err| format!("Erro ao mover para o cgroup: {}", err))?;
+    limits.write(&dir).map_err(|err| format!("Erro ao aplicar limites: {}", err))?;
+    Ok(dir.display().to_string())
+}
+
+fn is_locked(verdict: &Verdict, acknowledged: bool) -> bool {
+    match verdict {
+        Verdict::Allowed => false,
+        Verdict::NeedsConfirmation(_) => !acknowledged,
+        Verdict::ReadOnly | Verdict::Blocked(_) => true,
+    }
+}
+
+fn describe(result: std::io::Result<std::path::PathBuf>, success: &str) -> String {
+    match result {
+        Ok(dir) => format!("{}: {}", success, dir.display()),
+        Err(err) => format!("Erro: {}", err),
+    }
+}
+
+fn describe_oom(result: Result<i32, String>) -> String {
+    match result {
+        Ok(adj) => format!("oom_score_adj alterado para {}", adj),
+        Err(err) => err,
+    }
+}
+
+fn describe_signal(result: Result<(), String>, success: &str) -> String {
+    match result {
+        Ok(()) => success.to_string(),
+        Err(err) => err,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    settings::Settings,
+    protection::{ parse_protected, ProtectionMode },
+};
+
+#[derive(Props)]
+pub struct ProtectionPanelProps<'a> {
+    settings: &'a UseState<Settings>,
+}
+
+#[allow(non_snake_case)]
+pub fn ProtectionPanel<'a>(cx: Scope<'a, ProtectionPanelProps<'a>>) -> Element<'a> {
+    let protected_text = use_state(cx, || cx.props.settings.get().protected.join("\n"));
+    let message = use_state(cx, String::new);
+    let current = cx.props.settings.get();
+    let mode = ProtectionMode::parse(&current.protection_mode);
+    let (protected, errors) = parse_protected(&protected_text.get().lines().map(|entry_line| entry_line.to_string()).collect::<Vec<_>>());
+
+    let update = move |change: &dyn Fn(&mut Settings)| {
+        let mut settings = cx.props.settings.get().clone();
+        change(&mut settings);
+        message.set(match settings.save() {
+            Ok(()) => "Configuração salva".to_string(),
+            Err(err) => format!("Configuração ativa, mas não salva: {}", err),
+        });
+        cx.props.settings.set(settings);
+    };
+
+    render!(
+        div { class: "details",
+            h4 { "Modo somente leitura" },
+            label {
+                input { r#type: "checkbox", checked: current.read_only,
+                    onclick: move |_| update(&|settings| settings.read_only = !settings.read_only),
+                },
+                "Desativar todas as ações (demonstração e quiosque)"
+            },
+            h4 { "Processos protegidos" },
+            div { "Uma entrada por linha: pid:<n>, name:<nome>, user:<usuário ou uid>, cgroup:<prefixo> ou self" },
+            textarea { class: "wide-input rules", value: "{protected_text}", oninput: move |evt| protected_text.set(evt.value.clone()) },
+            for err in errors.iter() {
+                rsx!(div { class: "message", "{err}" })
+            }
+            div {
+                "Ações sobre processos protegidos: ",
+                select { onchange: move |evt| {
+                        let value = evt.data.value.clone();
+                        update(&|settings| settings.protection_mode = value.clone());
+                    },
+                    option { label: "Bloquear", value: "{ProtectionMode::Block.as_str()}", selected: mode == ProtectionMode::Block },
+                    option { label: "Pedir confirmação", value: "{ProtectionMode::Confirm.as_str()}", selected: mode == ProtectionMode::Confirm },
+                },
+                button {
+                    disabled: !errors.is_empty(),
+                    onclick: move |_| {
+                        let entries = protected_text.get().lines().filter(|entry_line| !entry_line.trim().is_empty()).map(|entry_line| entry_line.trim().to_string()).collect::<Vec<_>>();
+                        update(&|settings| settings.protected = entries.clone());
+                    },
+                    "Salvar lista"
+                },
+                span { class: "message", format! { " {} entrada(s) válidas. {}", protected.len(), message } },
+            },
+        }
+    )
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use dioxus::{ html::*, prelude::* };
+use dioxus_charts::LineChart;
+
+use crate::utils::{
+    settings::Settings,
+    sensors::Sensor,
+};
+
+#[derive(Props)]
+pub struct SensorsPanelProps<'a> {
+    sensors: &'a [Sensor],
+    settings: &'a UseState<Settings>,
+}
+
+fn format_value(value: Option<f64>) -> String {
+    value.map(|value| format!("{:.1}", value)).unwrap_or_else(|| "-".to_string())
+}
+
+#[allow(non_snake_case)]
+pub fn SensorsPanel<'a>(cx: Scope<'a, SensorsPanelProps<'a>>) -> Element<'a> {
+    let selected = use_state(cx, || None::<String>);
+    let margin = use_state(cx, || cx.props.settings.get().sensor_alert_margin.to_string());
+    let alerts_enabled = cx.props.settings.get().sensor_alerts;
+    let selected_sensor = selected.get().as_ref().and_then(|id| cx.props.sensors.iter().find(|sensor| sensor.id.eq(id)));
+
+    let update = move |alerts: bool| {
+        let mut settings = cx.props.settings.get().clone();
+        settings.sensor_alerts = alerts;
+        settings.sensor_alert_margin = margin.get().trim().parse().unwrap_or(settings.sensor_alert_margin);
+        if let Err(err) = settings.save() {
+            eprintln!("Erro ao salvar as configurações: {}", err);
+        }
+        cx.props.settings.set(settings);
+    };
+
+    render!(
+        div { class: "toolbar",
+            label {
+                input { r#type: "checkbox", checked: alerts_enabled, onclick: move |_| update(!alerts_enabled) },
+                "Alertar quando um sensor chegar a "
+            },
+            input { class: "small-input", value: "{margin}", oninput: move |evt| margin.set(evt.value.clone()), onchange: move |_| update(alerts_enabled) },
+            "% do valor crítico",
+        },
+        if cx.props.sensors.is_empty() {
+            rsx!(div { class: "message", "Nenhum sensor encontrado em /sys/class/hwmon" })
+        }
+        if let Some(sensor) = selected_sensor {
+            rsx!(
+                div { class: "details",
+                    h4 { format! { "{} {} ({})", sensor.chip, sensor.label, sensor.kind.unit() } },
+                    if sensor.history.len() >= 2 {
+                        rsx!(
+                            LineChart {
+                                width: "100%",
+                                height: "100%",
+                                padding_top: 30,
+                                padding_left: 50,
+                                padding_right: 90,
+                                padding_bottom: 30,
+                                show_grid_ticks: true,
+                                show_dotted_grid: false,
+                                series: vec![sensor.history.iter().map(|value| *value as f32).collect::<Vec<_>>()],
+                                labels: vec!["60".to_string(), "0".to_string()],
+                                series_labels: vec![sensor.label.clone()],
+                            }
+                        )
+                    } else {
+                        rsx!("Coletando amostras...")
+                    }
+                }
+            )
+        }
+        table {
+            thead {
+                tr {
+                    th { "Chip" },
+                    th { "Sensor" },
+                    th { "Tipo" },
+                    th { "Atual" },
+                    th { "Máximo" },
+                    th { "Crítico" },
+                    th { "ID" },
+                }
+            },
+            tbody {
+                for sensor in cx.props.sensors.iter() {
+                    rsx!(
+                        tr { key: "{sensor.id}",
+                            class: if selected.get().as_ref() == Some(&sensor.id) { "selected" } else { "" },
+                            onclick: move |_| selected.set(Some(sensor.id.clone())),
+                            td { "{sensor.chip}" },
+                            td { "{sensor.label}" },
+                            td { sensor.kind.label() },
+                            td { format! { "{:.1} {}", sensor.value, sensor.kind.unit() } },
+                            td { format_value(sensor.max) },
+                            td { format_value(sensor.critical) },
+                            td { "{sensor.id}" },
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
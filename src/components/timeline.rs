@@ -0,0 +1,85 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    history::ProcessHistory,
+    functions::{ cpu_colour, format_timestamp },
+};
+
+const WIDTH: f64 = 1000.0;
+const ROW_HEIGHT: f64 = 14.0;
+const MAX_BARS: usize = 200;
+
+#[derive(Props)]
+pub struct TimelineProps<'a> {
+    history: &'a UseRef<ProcessHistory>,
+    now: u64,
+}
+
+#[allow(non_snake_case)]
+pub fn Timeline<'a>(cx: Scope<'a, TimelineProps<'a>>) -> Element<'a> {
+    let text_filter = use_state(cx, String::new);
+    let hovered = use_state(cx, || None::<(u32, u64)>);
+
+    let history = cx.props.history.read();
+    let origin = history.started_at;
+    let total = history.entries.len();
+    let duration = cx.props.now.saturating_sub(origin).max(1) as f64;
+    let mut entries = history.entries.iter()
+        .filter(|entry| entry.name.contains(text_filter.get().as_str()) || entry.pid.to_string().contains(text_filter.get().as_str()))
+        .cloned()
+        .collect::<Vec<_>>();
+    drop(history);
+    entries.sort_by_key(|entry| (entry.start_time, entry.pid));
+    if entries.len() > MAX_BARS {
+        entries.drain(..entries.len() - MAX_BARS);
+    }
+    let height = (entries.len() as f64 * ROW_HEIGHT).max(ROW_HEIGHT);
+    let hovered_entry = hovered.get().and_then(|(pid, start)| entries.iter().find(|entry| entry.pid == pid && entry.start_time == start).cloned());
+    let bars = entries.into_iter().enumerate().map(|(idx, entry)| {
+        let start = entry.start_time.max(origin).saturating_sub(origin) as f64;
+        let end = entry.last_seen.saturating_sub(origin) as f64;
+        let x = start / duration * WIDTH;
+        let width = ((end - start) / duration * WIDTH).max(2.0);
+        (entry, x, idx as f64 * ROW_HEIGHT, width)
+    }).collect::<Vec<_>>();
+
+    render!(
+        div { class: "toolbar",
+            input { placeholder: "Nome ou PID", value: "{text_filter}", oninput: move |evt| text_filter.set(evt.value.clone()) },
+            span { format! { " Desde {} (UTC) - {} processos", format_timestamp(origin), total } },
+        },
+        div { class: "timeline-details",
+            if let Some(entry) = hovered_entry {
+                rsx!(
+                    format! { "{} ({}) - início {} - {} - CPU média {:.1}% / pico {:.1}% - memória pico {} MB",
+                        entry.name,
+                        entry.pid,
+                        format_timestamp(entry.start_time),
+                        if entry.exited { format!("fim {}", format_timestamp(entry.last_seen)) } else { "em execução".to_string() },
+                        entry.average_cpu(),
+                        entry.peak_cpu,
+                        entry.peak_memory,
+                    }
+                )
+            } else {
+                rsx!("Passe o mouse sobre uma barra para ver os detalhes")
+            }
+        },
+        svg { class: "timeline", view_box: "0 0 {WIDTH} {height}", preserve_aspect_ratio: "none", height: "{height}px", width: "100%",
+            for (entry, x, y, width) in bars {
+                rsx!(
+                    rect {
+                        key: "{entry.pid}-{entry.start_time}",
+                        x: "{x}",
+                        y: "{y}",
+                        width: "{width}",
+                        height: "{ROW_HEIGHT - 2.0}",
+                        fill: "{cpu_colour(entry.average_cpu())}",
+                        opacity: if entry.exited { "0.6" } else { "1" },
+                        onmouseenter: move |_| hovered.set(Some((entry.pid, entry.start_time))),
+                    }
+                )
+            }
+        }
+    )
+}
\ No newline at end of file
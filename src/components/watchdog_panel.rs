@@ -0,0 +1,87 @@
+use dioxus::{ html::*, prelude::* };
+
+use crate::utils::{
+    settings::Settings,
+    watchdog::{ parse_rules, WatchdogEntry },
+    functions::format_timestamp,
+};
+
+#[derive(Props)]
+pub struct WatchdogPanelProps<'a> {
+    settings: &'a UseState<Settings>,
+    log: &'a [WatchdogEntry],
+}
+
+#[allow(non_snake_case)]
+pub fn WatchdogPanel<'a>(cx: Scope<'a, WatchdogPanelProps<'a>>) -> Element<'a> {
+    let rules_text = use_state(cx, || cx.props.settings.get().watchdog_rules.join("\n"));
+    let message = use_state(cx, String::new);
+    let dry_run = cx.props.settings.get().watchdog_dry_run;
+    let (rules, errors) = parse_rules(&rules_text.get().lines().map(|rule_line| rule_line.to_string()).collect::<Vec<_>>());
+
+    render!(
+        div { class: "details",
+            h4 { "Regras" },
+            div { "Uma regra por linha, por exemplo: if name=~chrome and memory > 4 GiB for 30s then SIGTERM, if cmd=~--type=renderer then SIGTERM, ou renice any cc1plus to 19" },
+            textarea { class: "wide-input rules", value: "{rules_text}", oninput: move |evt| rules_text.set(evt.value.clone()) },
+            for err in errors.iter() {
+                rsx!(div { class: "message", "{err}" })
+            }
+            div {
+                label {
+                    input { r#type: "checkbox", checked: dry_run,
+                        onclick: move |_| {
+                            let mut settings = cx.props.settings.get().clone();
+                            settings.watchdog_dry_run = !settings.watchdog_dry_run;
+                            if let Err(err) = settings.save() {
+                                message.set(format!("Erro ao salvar: {}", err));
+                            }
+                            cx.props.settings.set(settings);
+                        },
+                    },
+                    "Simulação (não executa as ações)"
+                },
+                button {
+                    disabled: !errors.is_empty(),
+                    onclick: move |_| {
+                        let mut settings = cx.props.settings.get().clone();
+                        settings.watchdog_rules = rules_text.get().lines().filter(|rule_line| !rule_line.trim().is_empty()).map(|rule_line| rule_line.to_string()).collect();
+                        message.set(match settings.save() {
+                            Ok(()) => format!("{} regra(s) ativas", rules.len()),
+                            Err(err) => format!("Regras ativas, mas não salvas: {}", err),
+                        });
+                        cx.props.settings.set(settings);
+                    },
+                    "Salvar regras"
+                },
+                span { class: "message", " {message}" },
+            },
+        },
+        table {
+            thead {
+                tr {
+                    th { "Horário (UTC)" },
+                    th { "Regra" },
+                    th { "PID" },
+                    th { "Nome" },
+                    th { "Ação" },
+                    th { "Resultado" },
+                }
+            },
+            tbody {
+                for entry in cx.props.log.iter().rev() {
+                    rsx!(
+                        tr {
+                            td { format_timestamp(entry.timestamp) },
+                            td { "{entry.rule}" },
+                            td { "{entry.pid}" },
+                            td { "{entry.name}" },
+                            td { if entry.dry_run { format!("{} (simulado)", entry.action) } else { entry.action.clone() } },
+                            td { "{entry.outcome}" },
+                        }
+                    )
+                }
+            }
+        }
+    )
+}
\ No newline at end of file
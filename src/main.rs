@@ -1,4 +1,5 @@
 mod utils;
+mod components;
 
 use sysinfo::{*, SystemExt};
 use dioxus::{ html::*, prelude::* };
@@ -9,22 +10,67 @@ use futures::StreamExt;
 use futures_channel::mpsc::{ unbounded, UnboundedReceiver, UnboundedSender };
 use utils::app_props::Network;
 use std::cell::Cell;
+use std::sync::{ Arc, atomic::{ AtomicBool, Ordering }, mpsc };
+use std::path::Path;
+use std::time::{ Duration, Instant };
 
 use crate::utils::{
     sort::{ Sort, SortType },
     app_props::*,
-    functions::*
+    functions::*,
+    namespaces::{ Namespaces, NAMESPACE_KINDS, format_inode },
+    cgroup::{ process_cgroup, cgroup_dir, is_frozen },
+    kill_tree::kill_order,
+    tree::descendants,
+    oom::{ oom_candidates, read_oom_score, read_oom_score_adj },
+    events::{ diff_snapshots, ProcessEvent, MAX_EVENTS },
+    tracking::{ ProcessTable, RowStatus, TrackedRow },
+    proc_connector::{ ProcConnector, ConnectorTracker },
+    crashloop::{ detect_crash_loops, DEFAULT_RESTARTS, DEFAULT_WINDOW },
+    history::ProcessHistory,
+    state::{ read_state, read_wchan, state_label, is_flagged, summarize_uninterruptible },
+    launcher::LaunchedTask,
+    settings::Settings,
+    watchdog::{ parse_rules, Watchdog, WatchdogEntry, MAX_WATCHDOG_LOG },
+    protection::{ set_active_guard, Guard },
+    cpustat::{ breakdowns, read_cpu_times, CPU_HISTORY },
+    cpuinfo::read_frequency_mhz,
+    sensors::{ read_sensors, ComponentsSource, HwmonSource, Sensor },
+    power::{ power_summary, read_power, DischargeHistory, PowerStatus, POWER_SUPPLY_ROOT },
+    rapl::{ RaplMeter, RaplPower, RaplSource },
+    alerts::{ parse_alert_rules, sensor_alert_rules, AlertEvent, AlertMonitor, DesktopNotifier, Notifier, MAX_ALERT_HISTORY },
 };
+use crate::components::{
+    process_details::ProcessDetails,
+    event_log::EventLog,
+    timeline::Timeline,
+    launcher::{ LaunchDialog, TaskLogs },
+    watchdog_panel::WatchdogPanel,
+    alerts::{ AlertBanner, AlertPanel, BannerNotifier },
+    audit_log::AuditLog,
+    protection_panel::ProtectionPanel,
+    cpu_breakdown::CpuBreakdownChart,
+    cpu_heatmap::CpuHeatmap,
+    cpu_info::CpuInfoPanel,
+    sensors::SensorsPanel,
+    power::PowerPanel,
+    energy::EnergyPanel,
+};
+
+const CPU_CHART_MODES: [(&str, &str); 3] = [("aggregate", "Agregado"), ("cores", "Por núcleo"), ("heatmap", "Mapa de calor")];
 
 
 fn main() {
     let (sender_procs, receiver_procs) = unbounded();
     let other_procs = sender_procs.clone();
+    let (sender_events, receiver_events) = unbounded();
 
     std::thread::spawn(move || {
-        scan_processes(other_procs);
+        scan_processes(other_procs, sender_events);
     });
 
+    let (sender_launches, receiver_launches) = unbounded();
+
     let (sender_performance, receiver_performance) = unbounded();
     let other_performance = sender_performance.clone();
 
@@ -37,6 +83,9 @@ fn main() {
         AppProps {
             receiver_procs: Cell::new(Some(receiver_procs)),
             receiver_performance: Cell::new(Some(receiver_performance)),
+            receiver_events: Cell::new(Some(receiver_events)),
+            receiver_launches: Cell::new(Some(receiver_launches)),
+            sender_launches,
         },
         Config::default().with_window(
             WindowBuilder::new()
@@ -49,78 +98,331 @@ fn main() {
 }
 
 fn app(cx: Scope<AppProps>) -> Element {
+    let settings = use_state(cx, Settings::load);
     let current_tab = use_state(cx, || "tab-processes".to_string());
     let current_graph = use_state(cx, || "CPU".to_string());
     let current_filter = use_state(cx, || "".to_string());
-    let options = vec!["CPU".to_string(), "Memória".to_string(), "Network".to_string(), "Discos".to_string()];
+    let current_namespace = use_state(cx, || "pid".to_string());
+    let group_by_namespace = use_state(cx, || false);
+    let selected_pid = use_state(cx, || None::<u32>);
+    let state_filter = use_state(cx, || "Todos".to_string());
+    let crash_restarts = use_state(cx, || DEFAULT_RESTARTS.to_string());
+    let crash_window = use_state(cx, || DEFAULT_WINDOW.to_string());
+    let options = vec!["CPU".to_string(), "Memória".to_string(), "Network".to_string(), "Discos".to_string(), "Sensores".to_string(), "Energia".to_string()];
     
-    let current_processes = use_state(cx, || Vec::new());
+    let current_processes = use_state(cx, ProcessTable::default);
+    // Grows to thousands of entries; a ref is updated in place instead of cloned on every tick.
+    let process_history = use_ref(cx, ProcessHistory::default);
+    let watchdog_log = use_state(cx, Vec::<WatchdogEntry>::new);
     let _ = use_coroutine(cx, |_: UnboundedReceiver<()>| {
         let receiver = cx.props.receiver_procs.take();
         let current_processes = current_processes.to_owned();
+        let process_history = process_history.clone();
+        let settings = settings.to_owned();
+        let watchdog_log = watchdog_log.to_owned();
         async move {
             if let Some(mut receiver) = receiver {
+                let mut process_table = ProcessTable::default();
+                let mut watchdog = Watchdog::default();
+                let mut rules_text: Vec<String> = Vec::new();
+                let mut rules = Vec::new();
                 while let Some(procs) = receiver.next().await {
-                    current_processes.set(procs);
+                    let current_settings = settings.current();
+                    if current_settings.watchdog_rules.ne(&rules_text) {
+                        rules_text = current_settings.watchdog_rules.clone();
+                        rules = parse_rules(&rules_text).0;
+                    }
+                    let entries = watchdog.evaluate(&rules, &procs, current_settings.watchdog_dry_run, &Guard::new(&current_settings), now_secs());
+                    if !entries.is_empty() {
+                        watchdog_log.with_mut(|log| {
+                            log.extend(entries);
+                            if log.len() > MAX_WATCHDOG_LOG {
+                                let excess = log.len() - MAX_WATCHDOG_LOG;
+                                log.drain(..excess);
+                            }
+                        });
+                    }
+                    process_history.write().update(&procs, now_secs());
+                    process_table.update(procs, Instant::now());
+                    current_processes.set(process_table.clone());
+                }
+            }
+        }
+    });
+    let process_events = use_state(cx, Vec::<ProcessEvent>::new);
+    let _ = use_coroutine(cx, |_: UnboundedReceiver<()>| {
+        let receiver = cx.props.receiver_events.take();
+        let process_events = process_events.to_owned();
+        async move {
+            if let Some(mut receiver) = receiver {
+                while let Some(events) = receiver.next().await {
+                    process_events.with_mut(|current| {
+                        current.extend(events);
+                        if current.len() > MAX_EVENTS {
+                            let excess = current.len() - MAX_EVENTS;
+                            current.drain(..excess);
+                        }
+                    });
+                }
+            }
+        }
+    });
+    let launched_tasks = use_state(cx, Vec::<LaunchedTask>::new);
+    let show_launcher = use_state(cx, || false);
+    let _ = use_coroutine(cx, |_: UnboundedReceiver<()>| {
+        let receiver = cx.props.receiver_launches.take();
+        let launched_tasks = launched_tasks.to_owned();
+        async move {
+            if let Some(mut receiver) = receiver {
+                while let Some(update) = receiver.next().await {
+                    launched_tasks.with_mut(|tasks| {
+                        if let Some(task) = tasks.iter_mut().find(|task| task.id == update.id()) {
+                            task.apply(update);
+                        }
+                    });
                 }
             }
         }
     });
+    let crash_loops = detect_crash_loops(
+        process_events.get(),
+        crash_restarts.get().parse().unwrap_or(DEFAULT_RESTARTS),
+        crash_window.get().parse().unwrap_or(DEFAULT_WINDOW),
+        now_secs(),
+    );
     let sorting_procs = use_state(cx, || Sort{ field: "".to_string(), sort_type: SortType::Unset });
     let sort_procs = sorting_procs.get();
-    let procs = current_processes.get().to_vec();
-    let mut sorted_procs = procs.into_iter().filter(|proc| proc.name.contains(current_filter.as_str()) || proc.pid.to_string().contains(current_filter.as_str())).collect::<Vec<_>>();
+    let procs = current_processes.get().live();
+    let launched_pids = launched_tasks.get().iter()
+        .filter(|task| task.is_running())
+        .flat_map(|task| {
+            let mut pids = descendants(task.pid, &procs);
+            pids.push(task.pid);
+            pids
+        })
+        .collect::<Vec<_>>();
+    let selected_process = selected_pid.get().and_then(|pid| procs.iter().find(|proc| proc.pid.eq(&pid)).cloned());
+    let selected_descendants = selected_pid.get()
+        .map(|pid| kill_order(pid, &procs).into_iter().filter(|child| *child != pid).filter_map(|child| procs.iter().find(|proc| proc.pid == child).cloned()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let selected_parent = selected_process.as_ref().and_then(|proc| proc.parent).and_then(|ppid| procs.iter().find(|proc| proc.pid == ppid).cloned());
+    let zombie_count = procs.iter().filter(|proc| proc.state == 'Z').count();
+    let uninterruptible = summarize_uninterruptible(&procs);
+    let uninterruptible_count = uninterruptible.iter().map(|(_, count)| count).sum::<usize>();
+    let guard = Guard::new(settings.get());
+    set_active_guard(guard.clone());
+    let candidates = oom_candidates(&procs);
+    let candidate_pids = candidates.iter().map(|proc| proc.pid).collect::<Vec<_>>();
+    let namespace_kind = current_namespace.get().as_str();
+    let now = Instant::now();
+    let mut sorted_procs = current_processes.get().rows().iter().filter(|row| row.process.name.contains(current_filter.as_str()) || row.process.pid.to_string().contains(current_filter.as_str()) || row.process.namespaces.get(namespace_kind).to_string().eq(current_filter.as_str())).filter(|row| match state_filter.get().as_str() {
+        "Zumbis" => row.process.state == 'Z',
+        "D" => row.process.state == 'D',
+        "Zumbis e D" => is_flagged(row.process.state),
+        _ => true,
+    }).cloned().collect::<Vec<_>>();
     if sort_procs.sort_type.eq(&SortType::Asc) {
-        sorted_procs.sort_by(|p1, p2| MyProcess::new(p1).compare(MyProcess::new(p2), sort_procs.field.as_str()));
+        sorted_procs.sort_by(|r1, r2| MyProcess::new(&r1.process).compare(MyProcess::new(&r2.process), sort_procs.field.as_str()));
     } else if sort_procs.sort_type.eq(&SortType::Desc) {
-        sorted_procs.sort_by(|p1, p2| MyProcess::new(p2).compare(MyProcess::new(p1), sort_procs.field.as_str()));
+        sorted_procs.sort_by(|r1, r2| MyProcess::new(&r2.process).compare(MyProcess::new(&r1.process), sort_procs.field.as_str()));
     }
+    let namespace_field = format!("ns:{}", namespace_kind);
+    let proc_groups = group_processes(sorted_procs, *group_by_namespace.get(), namespace_kind).into_iter()
+        .map(|(inode, group)| (
+            inode.map(|inode| format!("{} {} - {} processos", namespace_kind, format_inode(inode), group.len())),
+            group.into_iter().map(|row| {
+                let class = row_class(row.status(now), selected_pid.get().eq(&Some(row.process.pid)), candidate_pids.contains(&row.process.pid));
+                let launched = launched_pids.contains(&row.process.pid);
+                (row.key(), row.process, class, launched)
+            }).collect::<Vec<_>>(),
+        ))
+        .collect::<Vec<_>>();
 
     let current_performance = use_state(cx, || Performance::default());
+    let active_alerts = use_state(cx, Vec::<AlertEvent>::new);
+    let alert_history = use_state(cx, Vec::<AlertEvent>::new);
     let _ = use_coroutine(cx, |_: UnboundedReceiver<()>| {
         let receiver = cx.props.receiver_performance.take();
         let current_performance = current_performance.to_owned();
+        let current_processes = current_processes.to_owned();
+        let settings = settings.to_owned();
+        let active_alerts = active_alerts.to_owned();
+        let alert_history = alert_history.to_owned();
         async move {
             if let Some(mut receiver) = receiver {
+                let mut monitor = AlertMonitor::default();
+                let mut rules_text: Vec<String> = Vec::new();
+                let mut rules = Vec::new();
                 while let Some(performace) = receiver.next().await {
+                    let current_settings = settings.current();
+                    if current_settings.alert_rules.ne(&rules_text) {
+                        rules_text = current_settings.alert_rules.clone();
+                        rules = parse_alert_rules(&rules_text).0;
+                    }
+                    let mut notifiers: Vec<Box<dyn Notifier>> = vec![Box::new(BannerNotifier { active: active_alerts.clone() })];
+                    if current_settings.alert_desktop {
+                        notifiers.push(Box::new(DesktopNotifier));
+                    }
+                    let mut active_rules = rules.clone();
+                    if current_settings.sensor_alerts {
+                        active_rules.extend(sensor_alert_rules(&performace.sensors, current_settings.sensor_alert_margin));
+                    }
+                    let events = monitor.evaluate(&active_rules, &performace, &current_processes.current().live(), now_secs(), &mut notifiers);
+                    if !events.is_empty() {
+                        alert_history.with_mut(|history| {
+                            history.extend(events);
+                            if history.len() > MAX_ALERT_HISTORY {
+                                let excess = history.len() - MAX_ALERT_HISTORY;
+                                history.drain(..excess);
+                            }
+                        });
+                    }
                     current_performance.set(performace);
                 }
             }
         }
     });
     let performance = current_performance.get();
+    // Battery drain shown next to the current CPU load in the process toolbar.
+    let power_line = power_summary(&performance.power)
+        .map(|battery_line| format!(" {} - CPU {:.0}%", battery_line, aggregate_uses(&performance.cpus).last().unwrap_or(&0.0)))
+        .unwrap_or_default();
     
     cx.render(rsx!(
         style { include_str!("./assets/styles.css") },
         style { include_str!("./assets/options.css") },
         link { rel: "stylesheet", href:"https://fonts.googleapis.com/icon?family=Material+Icons" },
         body {
+            AlertBanner { active: active_alerts },
             section { class: "tabs wrapper",
                 input { name: "tab-processes", id: "tab-processes", r#type: "radio", class: "tabs-radio",
                     checked: is_tab(current_tab, "tab-processes") ,
                     onclick: move |_| set_tab(current_tab, "tab-processes"),
                 },
                 label { r#for: "tab-processes", class: "tabs-label", "Processos" },
-                table { class: "tabs-content",
-                    thead {
-                        tr {
-                            th { onclick: move |_| set_sorting(sorting_procs, "name"), "Nome", sort_procs.clone().sorting(cx, "name".to_string())},
-                            th { onclick: move |_| set_sorting(sorting_procs, "pid"), "PID", sort_procs.clone().sorting(cx, "pid".to_string())},
-                            th { onclick: move |_| set_sorting(sorting_procs, "cpu_usage"), "CPU", sort_procs.clone().sorting(cx, "cpu_usage".to_string())},
-                            th { onclick: move |_| set_sorting(sorting_procs, "memory"), "Memória", sort_procs.clone().sorting(cx,"memory".to_string())},
+                div { class: "tabs-content",
+                    div { class: "toolbar",
+                        "Namespace: ",
+                        select { onchange: move |evt| current_namespace.set(evt.data.value.clone()),
+                            for kind in NAMESPACE_KINDS {
+                                rsx!(
+                                    option { label: "{kind}", value: "{kind}", selected: current_namespace.get().eq(kind) }
+                                )
+                            }
+                        },
+                        label {
+                            input { r#type: "checkbox", checked: *group_by_namespace.get(),
+                                onclick: move |_| group_by_namespace.set(!group_by_namespace.get()),
+                            },
+                            "Agrupar por namespace"
+                        },
+                        button { disabled: settings.get().read_only, onclick: move |_| show_launcher.set(true), "Novo processo" },
+                        " Estado: ",
+                        select { onchange: move |evt| state_filter.set(evt.data.value.clone()),
+                            for opt in ["Todos", "Zumbis", "D", "Zumbis e D"] {
+                                rsx!(option { label: "{opt}", value: "{opt}", selected: state_filter.get().eq(opt) })
+                            }
                         },
+                        " Loop de reinício: mais de ",
+                        input { class: "small-input", value: "{crash_restarts}", oninput: move |evt| crash_restarts.set(evt.value.clone()) },
+                        " reinícios em ",
+                        input { class: "small-input", value: "{crash_window}", oninput: move |evt| crash_window.set(evt.value.clone()) },
+                        " s",
+                        "{power_line}",
                     },
-                    tbody {
-                        for proc in sorted_procs {
+                    if *show_launcher.get() && !settings.get().read_only {
+                        rsx!(LaunchDialog { tasks: launched_tasks, updates: &cx.props.sender_launches, on_close: move |_| show_launcher.set(false) })
+                    }
+                    if zombie_count > 0 || uninterruptible_count > 0 {
+                        rsx!(
+                            div { class: "state-warning",
+                                if zombie_count > 0 {
+                                    rsx!(div { format! { "{} processo(s) zumbi aguardando o pai", zombie_count } })
+                                }
+                                if uninterruptible_count > 0 {
+                                    rsx!(div {
+                                        format! { "{} processo(s) em espera ininterrupta (D), geralmente E/S de disco ou NFS travada: ", uninterruptible_count },
+                                        uninterruptible.iter().map(|(wchan, count)| format!("{} ({})", wchan, count)).collect::<Vec<_>>().join(", ")
+                                    })
+                                }
+                            }
+                        )
+                    }
+                    if !crash_loops.is_empty() {
+                        rsx!(
+                            div { class: "crash-loops",
+                                h4 { "Processos reiniciando em loop" },
+                                for crash_loop in crash_loops {
+                                    rsx!(
+                                        div {
+                                            span { class: "badge crash-loop", format! { "{}x", crash_loop.restarts } },
+                                            format! { " {} - última saída: {} - vida média: {}",
+                                                crash_loop.cmd,
+                                                format_timestamp(crash_loop.last_exit),
+                                                crash_loop.average_lifetime.map(|secs| format!("{:.1} s", secs)).unwrap_or_else(|| "-".to_string()),
+                                            }
+                                        }
+                                    )
+                                }
+                            }
+                        )
+                    }
+                    table {
+                        thead {
+                            tr {
+                                th { onclick: move |_| set_sorting(sorting_procs, "name"), "Nome", sort_procs.clone().sorting(cx, "name".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, "pid"), "PID", sort_procs.clone().sorting(cx, "pid".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, "state"), "Estado", sort_procs.clone().sorting(cx, "state".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, "cpu_usage"), "CPU", sort_procs.clone().sorting(cx, "cpu_usage".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, "memory"), "Memória", sort_procs.clone().sorting(cx,"memory".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, "oom_score"), "OOM", sort_procs.clone().sorting(cx, "oom_score".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, "oom_score_adj"), "OOM adj", sort_procs.clone().sorting(cx, "oom_score_adj".to_string())},
+                                th { onclick: move |_| set_sorting(sorting_procs, &namespace_field), "NS {namespace_kind}", sort_procs.clone().sorting(cx, format!("ns:{}", namespace_kind))},
+                            },
+                        },
+                        for (group_title, group) in proc_groups {
                             rsx!(
-                                tr {
-                                    td { "{proc.name}" },
-                                    td { "{proc.pid}" },
-                                    td { "{proc.cpu_usage}" },
-                                    td { "{proc.memory}" },
-                                },
+                                tbody {
+                                    if let Some(group_title) = group_title {
+                                        rsx!(
+                                            tr { class: "group-header",
+                                                td { colspan: "8", group_title },
+                                            }
+                                        )
+                                    }
+                                    for (row_key, proc, class, launched) in group {
+                                        rsx!(
+                                            tr { key: "{row_key}", class: "{class}",
+                                                onclick: move |_| selected_pid.set(Some(proc.pid)),
+                                                td { "{proc.name}",
+                                                    if proc.frozen {
+                                                        rsx!(span { class: "badge frozen", "congelado" })
+                                                    }
+                                                    if launched {
+                                                        rsx!(span { class: "badge launched", "lançado" })
+                                                    }
+                                                },
+                                                td { "{proc.pid}" },
+                                                td { title: state_label(proc.state),
+                                                    "{proc.state}",
+                                                    if is_flagged(proc.state) {
+                                                        rsx!(span { class: "badge state-{proc.state}", state_label(proc.state) })
+                                                    }
+                                                },
+                                                td { "{proc.cpu_usage}" },
+                                                td { "{proc.memory}" },
+                                                td { "{proc.oom_score}" },
+                                                td { "{proc.oom_score_adj}" },
+                                                td { format_inode(proc.namespaces.get(namespace_kind)) },
+                                            },
+                                        )
+                                    }
+                                }
                             )
                         }
+                    },
+                    if let Some(process) = selected_process {
+                        rsx!(ProcessDetails { key: "{process.pid}", process: process, descendants: selected_descendants, parent: selected_parent, guard: guard })
                     }
                 },
 
@@ -138,30 +440,55 @@ fn app(cx: Scope<AppProps>) -> Element {
                         }
                     },
                     match current_graph.get().as_str() {
-                        "CPU" => rsx!(div {
-                            if !performance.cpus.is_empty() {
-                                rsx!(LineChart {
-                                    width: "100%",
-                                    height: "100%",
-                                    padding_top: 30,
-                                    padding_left: 50,
-                                    padding_right: 90,
-                                    padding_bottom: 30,
-                                    show_grid_ticks: true,
-                                    show_dotted_grid: false,
-                                    series: match performance.cpus.is_empty() {
-                                        false => performance.cpus.iter().map(|c| c.uses.clone()).collect::<Vec<_>>(),
-                                        true => Vec::new()
-                                    },
-                                    labels: vec!["60".to_string(), "0".to_string()],
-                                    series_labels: match performance.cpus.is_empty() {
-                                        false => performance.cpus.iter().map(|c| c.name.clone()).collect::<Vec<_>>(),
-                                        true => Vec::new(),
+                        "CPU" => rsx!(div { class: "cpu-layout",
+                            div { class: "cpu-charts",
+                                if !performance.power.batteries.is_empty() {
+                                    rsx!(PowerPanel { power: &performance.power, cpu_load: aggregate_uses(&performance.cpus) })
+                                }
+                                select { onchange: move |evt| {
+                                        let mut new_settings = settings.get().clone();
+                                        new_settings.cpu_chart_mode = evt.data.value.clone();
+                                        if let Err(err) = new_settings.save() {
+                                            eprintln!("Erro ao salvar as configurações: {}", err);
+                                        }
+                                        settings.set(new_settings);
                                     },
-                                })
-                            } else {
-                                rsx!("Carregando...")
-                            }
+                                    for (mode, mode_label) in CPU_CHART_MODES {
+                                        rsx!(option { label: "{mode_label}", value: "{mode}", selected: settings.get().cpu_chart_mode.eq(mode) })
+                                    }
+                                },
+                                if performance.cpus.is_empty() {
+                                    rsx!("Carregando...")
+                                } else {
+                                    match settings.get().cpu_chart_mode.as_str() {
+                                        "cores" => rsx!(
+                                            div { class: "cpu-breakdown-grid",
+                                                for cpu in performance.cpus.iter() {
+                                                    rsx!(CpuBreakdownChart { key: "{cpu.name}", name: &cpu.name, samples: &cpu.breakdown, height: 80 })
+                                                }
+                                            }
+                                        ),
+                                        "heatmap" => rsx!(CpuHeatmap { cpus: &performance.cpus }),
+                                        _ => rsx!(
+                                            LineChart {
+                                                width: "100%",
+                                                height: "100%",
+                                                padding_top: 30,
+                                                padding_left: 50,
+                                                padding_right: 90,
+                                                padding_bottom: 30,
+                                                show_grid_ticks: true,
+                                                show_dotted_grid: false,
+                                                series: vec![aggregate_uses(&performance.cpus)],
+                                                labels: vec!["60".to_string(), "0".to_string()],
+                                                series_labels: vec![format!("{} CPUs", performance.cpus.len())],
+                                            },
+                                            CpuBreakdownChart { name: "Total", samples: &performance.total_breakdown, height: 160 },
+                                        ),
+                                    }
+                                }
+                            },
+                            CpuInfoPanel { cpus: &performance.cpus },
                         }),
                         "Memória" => rsx!(div { 
                             div { style: "width: 100%; overflow: hidden;",
@@ -169,6 +496,12 @@ fn app(cx: Scope<AppProps>) -> Element {
                                     "Memória"
                                     div { format! { "Total: {:.02} GiB", performance.mem.total} },
                                     div { format! { "Em uso: {:.02} GiB", performance.mem.used} },
+                                    div { class: "oom-list",
+                                        "Primeiros alvos do OOM killer:"
+                                        for proc in candidates.iter() {
+                                            rsx!(div { format! { "{} ({}) - oom_score {} / adj {}", proc.name, proc.pid, proc.oom_score, proc.oom_score_adj } })
+                                        }
+                                    }
                                 }
                                 div { style: "margin-left: 15px; ",
                                     PieChart {
@@ -236,11 +569,78 @@ fn app(cx: Scope<AppProps>) -> Element {
                                 }
                             }
                         ),
+                        "Sensores" => rsx!(SensorsPanel { sensors: &performance.sensors, settings: settings }),
+                        "Energia" => rsx!(EnergyPanel { energy: &performance.energy, processes: current_processes.get(), settings: settings }),
                         _ => rsx!(div { "Deu merda" }),
                     }
                 },
                 
-                input { class: "search", name: "filter", id: "filter", disabled: is_tab(current_tab, "tab-performance"),
+                input { name: "tab-events", id: "tab-events", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-events"),
+                    onclick: move |_| set_tab(current_tab, "tab-events"),
+                },
+                label { r#for: "tab-events", class: "tabs-label", "Eventos" },
+                div { class: "tabs-content",
+                    EventLog { events: process_events.get() },
+                },
+
+                input { name: "tab-timeline", id: "tab-timeline", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-timeline"),
+                    onclick: move |_| set_tab(current_tab, "tab-timeline"),
+                },
+                label { r#for: "tab-timeline", class: "tabs-label", "Linha do tempo" },
+                div { class: "tabs-content",
+                    Timeline { history: process_history, now: now_secs() },
+                },
+
+                input { name: "tab-tasks", id: "tab-tasks", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-tasks"),
+                    onclick: move |_| set_tab(current_tab, "tab-tasks"),
+                },
+                label { r#for: "tab-tasks", class: "tabs-label", "Tarefas" },
+                div { class: "tabs-content",
+                    TaskLogs { tasks: launched_tasks.get() },
+                },
+
+                input { name: "tab-watchdog", id: "tab-watchdog", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-watchdog"),
+                    onclick: move |_| set_tab(current_tab, "tab-watchdog"),
+                },
+                label { r#for: "tab-watchdog", class: "tabs-label", "Vigia" },
+                div { class: "tabs-content",
+                    WatchdogPanel { settings: settings, log: watchdog_log.get() },
+                },
+
+                input { name: "tab-alerts", id: "tab-alerts", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-alerts"),
+                    onclick: move |_| set_tab(current_tab, "tab-alerts"),
+                },
+                label { r#for: "tab-alerts", class: "tabs-label", "Alertas" },
+                div { class: "tabs-content",
+                    AlertPanel { settings: settings, history: alert_history.get() },
+                },
+
+                input { name: "tab-audit", id: "tab-audit", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-audit"),
+                    onclick: move |_| set_tab(current_tab, "tab-audit"),
+                },
+                label { r#for: "tab-audit", class: "tabs-label", "Auditoria" },
+                div { class: "tabs-content",
+                    if is_tab(current_tab, "tab-audit") {
+                        rsx!(AuditLog {})
+                    }
+                },
+
+                input { name: "tab-protection", id: "tab-protection", r#type: "radio", class: "tabs-radio",
+                    checked: is_tab(current_tab, "tab-protection"),
+                    onclick: move |_| set_tab(current_tab, "tab-protection"),
+                },
+                label { r#for: "tab-protection", class: "tabs-label", "Proteção" },
+                div { class: "tabs-content",
+                    ProtectionPanel { settings: settings },
+                },
+
+                input { class: "search", name: "filter", id: "filter", disabled: !is_tab(current_tab, "tab-processes"),
                     placeholder: "Nome ou PID",
                     oninput: move |evt| current_filter.set(evt.value.clone()),
                 }
@@ -249,6 +649,24 @@ fn app(cx: Scope<AppProps>) -> Element {
     ))
 }
 
+// Average over all cores at each sample; cores share the same sampling ticks.
+fn aggregate_uses(cpus: &[MyCpu]) -> Vec<f32> {
+    let samples = cpus.iter().map(|cpu| cpu.uses.len()).min().unwrap_or(0);
+    (0..samples)
+        .map(|idx| {
+            let total = cpus.iter().map(|cpu| cpu.uses[cpu.uses.len() - samples + idx]).sum::<f32>();
+            total / cpus.len() as f32
+        })
+        .collect()
+}
+
+fn push_sample<T>(samples: &mut Vec<T>, sample: T) {
+    if samples.len() >= CPU_HISTORY {
+        samples.remove(0);
+    }
+    samples.push(sample);
+}
+
 fn set_tab(tab: &UseState<String>, value: &str) {
     tab.set(value.to_string());
 }
@@ -257,6 +675,38 @@ fn is_tab(tab: &UseState<String>, value: &str) -> bool {
     return tab.get().eq(value);
 }
 
+fn row_class(status: RowStatus, selected: bool, oom_candidate: bool) -> String {
+    let mut classes = Vec::new();
+    match status {
+        RowStatus::New => classes.push("row-new"),
+        RowStatus::Exited => classes.push("row-exited"),
+        RowStatus::Alive => {},
+    }
+    if selected {
+        classes.push("selected");
+    }
+    if oom_candidate {
+        classes.push("oom-candidate");
+    }
+    classes.join(" ")
+}
+
+fn group_processes(rows: Vec<TrackedRow>, group: bool, kind: &str) -> Vec<(Option<u64>, Vec<TrackedRow>)> {
+    if !group {
+        return vec![(None, rows)];
+    }
+    let mut groups: Vec<(Option<u64>, Vec<TrackedRow>)> = Vec::new();
+    for row in rows {
+        let inode = row.process.namespaces.get(kind);
+        match groups.iter_mut().find(|(key, _)| key.eq(&Some(inode))) {
+            Some((_, members)) => members.push(row),
+            None => groups.push((Some(inode), vec![row])),
+        }
+    }
+    groups.sort_by_key(|(inode, _)| *inode);
+    groups
+}
+
 fn set_sorting(sorting: &UseState<Sort>, field_to_sort: &str) {
     if sorting.get().field.ne(field_to_sort) {
         sorting.set(Sort {field: field_to_sort.to_string(), sort_type: SortType::Desc});
@@ -270,13 +720,37 @@ fn set_sorting(sorting: &UseState<Sort>, field_to_sort: &str) {
     sorting.set(Sort {field: field_to_sort.to_string(), sort_type});
 }
 
-fn scan_processes(sender: UnboundedSender<Vec<MyProcess>>) {
+fn scan_processes(sender: UnboundedSender<Vec<MyProcess>>, events_sender: UnboundedSender<Vec<ProcessEvent>>) {
+    let (wake_sender, wake_receiver) = mpsc::channel();
+    let connector_live = Arc::new(AtomicBool::new(false));
+    match ProcConnector::open() {
+        Ok(connector) => {
+            let events_sender = events_sender.clone();
+            let connector_live = connector_live.clone();
+            std::thread::spawn(move || {
+                listen_proc_connector(connector, events_sender, wake_sender, connector_live);
+            });
+        }
+        Err(err) => eprintln!("proc connector indisponível, usando polling: {}", err),
+    }
+
+    let mut previous: Vec<MyProcess> = Vec::new();
     loop {
         let mut procs: Vec<MyProcess> = Vec::new();
+        let mut frozen_cgroups: Vec<(String, bool)> = Vec::new();
         let mut sys_info = System::new_with_specifics(RefreshKind::new().with_processes(ProcessRefreshKind::everything()));
         sys_info.refresh_cpu();
         for process in sys_info.processes() {
-            
+            let cgroup = process_cgroup(process.1.pid().as_u32()).unwrap_or_default();
+            let frozen = match frozen_cgroups.iter().find(|(known, _)| known.eq(&cgroup)) {
+                Some((_, frozen)) => *frozen,
+                None => {
+                    let frozen = is_frozen(&cgroup_dir(&cgroup));
+                    frozen_cgroups.push((cgroup.clone(), frozen));
+                    frozen
+                }
+            };
+            let state = read_state(process.1.pid().as_u32());
             let proc = MyProcess {
                 pid: process.1.pid().as_u32(),
                 name: process.1.name().to_string(),
@@ -284,16 +758,80 @@ fn scan_processes(sender: UnboundedSender<Vec<MyProcess>>) {
                 cpu_usage: process.1.cpu_usage(),
                 read_bytes: process.1.disk_usage().read_bytes,
                 written_bytes: process.1.disk_usage().written_bytes,
+                namespaces: Namespaces::read(process.1.pid().as_u32()),
+                parent: process.1.parent().map(|pid| pid.as_u32()),
+                cgroup,
+                frozen,
+                oom_score: read_oom_score(process.1.pid().as_u32()),
+                oom_score_adj: read_oom_score_adj(process.1.pid().as_u32()),
+                start_time: process.1.start_time(),
+                cmd: process.1.cmd().join(" "),
+                state,
+                wchan: if state == 'D' { read_wchan(process.1.pid().as_u32()) } else { String::new() },
             };
             procs.push(proc);
         }
+        // While the connector is delivering events it is the source of truth for the log.
+        if !previous.is_empty() && !connector_live.load(Ordering::Relaxed) {
+            let events = diff_snapshots(&previous, &procs, now_secs());
+            if !events.is_empty() {
+                let _ = events_sender.unbounded_send(events);
+            }
+        }
+        previous = procs.clone();
         let _ = sender.unbounded_send(procs);
-        std::thread::sleep(std::time::Duration::from_secs(1))
+        match wake_receiver.recv_timeout(Duration::from_secs(1)) {
+            Ok(()) => {
+                std::thread::sleep(Duration::from_millis(250));
+                while wake_receiver.try_recv().is_ok() {}
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => std::thread::sleep(Duration::from_secs(1)),
+        }
+    }
+}
+
+fn listen_proc_connector(connector: ProcConnector, events_sender: UnboundedSender<Vec<ProcessEvent>>, wake_sender: mpsc::Sender<()>, live: Arc<AtomicBool>) {
+    let mut tracker = ConnectorTracker::new();
+    loop {
+        match connector.receive() {
+            Ok(raw_events) => {
+                live.store(true, Ordering::Relaxed);
+                let events = raw_events.into_iter().filter_map(|event| tracker.handle(event)).collect::<Vec<_>>();
+                if !events.is_empty() {
+                    let _ = events_sender.unbounded_send(events);
+                    let _ = wake_sender.send(());
+                }
+            }
+            // The kernel dropped events because we fell behind; the socket itself is still fine.
+            Err(err) if err.raw_os_error() == Some(libc::ENOBUFS) => {
+                let events = tracker.resync();
+                if !events.is_empty() {
+                    let _ = events_sender.unbounded_send(events);
+                    let _ = wake_sender.send(());
+                }
+            }
+            Err(err) => {
+                eprintln!("proc connector encerrado, voltando ao polling: {}", err);
+                live.store(false, Ordering::Relaxed);
+                return;
+            }
+        }
     }
 }
 
 fn scan_performance(sender: UnboundedSender<Performance>) {
-    // let mut current_cpus: Vec<MyCpu> = Vec::new();
+    let mut current_cpus: Vec<MyCpu> = Vec::new();
+    let mut total_breakdown = Vec::new();
+    let mut previous_times = read_cpu_times();
+    let hwmon = HwmonSource::default();
+    let mut sensors: Vec<Sensor> = Vec::new();
+    let mut power = PowerStatus::default();
+    let mut discharge = DischargeHistory::default();
+    let rapl = RaplSource::default();
+    let mut rapl_meter = RaplMeter::default();
+    let mut energy: Vec<RaplPower> = Vec::new();
+    let mut last_sample = Instant::now();
     loop {
         let mut sys_info = System::new_with_specifics(RefreshKind::everything().without_processes());
         sys_info.refresh_all();
@@ -326,29 +864,55 @@ fn scan_performance(sender: UnboundedSender<Performance>) {
             });
         }
 
-        // let other_cpus = current_cpus.to_vec();
+        let times = read_cpu_times();
+        let cpu_breakdowns = breakdowns(&previous_times, &times);
+        previous_times = times;
+        if let Some((_, total)) = cpu_breakdowns.iter().find(|(name, _)| name.eq("cpu")) {
+            push_sample(&mut total_breakdown, *total);
+        }
+
         let mut new_cpus = Vec::new();
         for cpu in sys_info.cpus() {
-            // let founded = other_cpus.to_vec().into_iter().find(|c| c.name.eq(cpu.name()));
-            // if let Some(mut founded) = founded {
-            //     if founded.uses.len().eq(&60) {
-            //         founded.uses.remove(0);
-            //     }
-
-            //     founded.uses.push(cpu.cpu_usage());
-            //     current_cpus.push(founded);
-            // } else {
-                new_cpus.push(MyCpu {
-                    name: cpu.name().to_string(),
-                    uses: vec![cpu.cpu_usage()],
-                });
-            // }
+            let mut founded = current_cpus.iter().find(|c| c.name.eq(cpu.name())).cloned().unwrap_or_else(|| MyCpu {
+                name: cpu.name().to_string(),
+                uses: Vec::new(),
+                breakdown: Vec::new(),
+                frequencies: Vec::new(),
+            });
+            push_sample(&mut founded.uses, cpu.cpu_usage());
+            push_sample(&mut founded.frequencies, read_frequency_mhz(cpu.name()).unwrap_or(cpu.frequency()));
+            if let Some((_, breakdown)) = cpu_breakdowns.iter().find(|(name, _)| name.eq(cpu.name())) {
+                push_sample(&mut founded.breakdown, *breakdown);
+            }
+            new_cpus.push(founded);
+        }
+        current_cpus = new_cpus.clone();
+
+        let mut new_sensors = read_sensors(&[&hwmon, &ComponentsSource { system: &sys_info }]);
+        for sensor in new_sensors.iter_mut() {
+            sensor.history = sensors.iter().find(|known| known.id.eq(&sensor.id)).map(|known| known.history.clone()).unwrap_or_default();
+            push_sample(&mut sensor.history, sensor.value);
+        }
+        sensors = new_sensors;
+
+        let mut new_power = read_power(Path::new(POWER_SUPPLY_ROOT));
+        discharge.update(&mut new_power, now_secs());
+        for battery in new_power.batteries.iter_mut() {
+            battery.power_history = power.batteries.iter().find(|known| known.name.eq(&battery.name)).map(|known| known.power_history.clone()).unwrap_or_default();
+            push_sample(&mut battery.power_history, battery.power_w.unwrap_or(0.0));
+        }
+        power = new_power;
+
+        let mut new_energy = rapl_meter.sample(rapl.read(), last_sample.elapsed().as_secs_f64());
+        last_sample = Instant::now();
+        for zone in new_energy.iter_mut() {
+            zone.history = energy.iter().find(|known| known.id.eq(&zone.id)).map(|known| known.history.clone()).unwrap_or_default();
+            push_sample(&mut zone.history, zone.watts);
         }
-        // for idx in 0..sys_info.cpus().len() - 1 {
-        //     current_cpus.remove(idx);
-        // }
+        energy = new_energy;
         let _ = sender.unbounded_send(Performance {
             cpus: new_cpus,
+            total_breakdown: total_breakdown.clone(),
             mem: Mem {
                 total: sys_info.total_memory() / 1000000000,
                 used: sys_info.used_memory() / 1000000000,
@@ -361,6 +925,9 @@ fn scan_performance(sender: UnboundedSender<Performance>) {
             },
             networks: struct_networks,
             disks: struct_disks,
+            sensors: sensors.clone(),
+            power: power.clone(),
+            energy: energy.clone(),
         });
         print!("passei aqui");
         std::thread::sleep(std::time::Duration::from_secs(1))
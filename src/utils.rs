@@ -1,3 +1,31 @@
 pub mod sort;
 pub mod app_props;
-pub mod functions;
\ No newline at end of file
+pub mod functions;
+pub mod namespaces;
+pub mod cgroup;
+pub mod tree;
+pub mod limits;
+pub mod oom;
+pub mod events;
+pub mod tracking;
+pub mod proc_connector;
+pub mod crashloop;
+pub mod history;
+pub mod state;
+pub mod signals;
+pub mod kill_tree;
+pub mod restart;
+pub mod launcher;
+pub mod priority;
+pub mod settings;
+pub mod watchdog;
+pub mod alerts;
+pub mod audit;
+pub mod protection;
+pub mod helper_protocol;
+pub mod helper;
+pub mod cpustat;
+pub mod cpuinfo;
+pub mod sensors;
+pub mod power;
+pub mod rapl;
\ No newline at end of file
@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+use std::process::{ Command, Stdio };
+
+use super::app_props::{ MyProcess, Performance };
+use super::sensors::Sensor;
+use super::watchdog::parse_duration;
+
+pub const MAX_ALERT_HISTORY: usize = 1000;
+
+#[derive(Clone, PartialEq)]
+pub enum Metric {
+    Cpu,
+    Core(String),
+    Memory,
+    Swap,
+    DiskFree(String),
+    Network(String),
+    ProcessCpu(String),
+    ProcessMemory(String),
+    Sensor(String),
+}
+
+#[derive(Clone, PartialEq)]
+pub struct AlertRule {
+    pub text: String,
+    pub metric: Metric,
+    pub above: bool,
+    pub threshold: f64,
+    pub sustain: u64,
+    pub hysteresis: f64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum AlertKind {
+    Fired,
+    Resolved,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct AlertEvent {
+    pub kind: AlertKind,
+    pub timestamp: u64,
+    pub rule: String,
+    // None when the metric stopped reporting (a sensor or interface went away).
+    pub value: Option<f64>,
+    pub unit: &'static str,
+}
+
+pub trait Notifier {
+    fn notify(&mut self, event: &AlertEvent);
+}
+
+// Freedesktop notifications through the session bus, with notify-send as a fallback.
+pub struct DesktopNotifier;
+
+struct RuleState {
+    since: Option<u64>,
+    active: bool,
+}
+
+#[derive(Default)]
+pub struct AlertMonitor {
+    states: HashMap<String, RuleState>,
+    network_totals: HashMap<String, (u64, u64)>,
+}
+
+impl Metric {
+    fn parse(text: &str) -> Result<Metric, String> {
+        let (kind, target) = match text.split_once(':') {
+            Some((kind, target)) => (kind, Some(target.to_string())),
+            None => (text, None),
+        };
+        match (kind, target) {
+            ("cpu", None) => Ok(Metric::Cpu),
+            ("cpu", Some(core)) => Ok(Metric::Core(core)),
+            ("mem", None) => Ok(Metric::Memory),
+            ("swap", None) => Ok(Metric::Swap),
+            ("disk", Some(mount)) => Ok(Metric::DiskFree(mount)),
+            ("net", Some(interface)) => Ok(Metric::Network(interface)),
+            ("proc-cpu", Some(name)) => Ok(Metric::ProcessCpu(name)),
+            ("proc-mem", Some(name)) => Ok(Metric::ProcessMemory(name)),
+            ("sensor", Some(id)) => Ok(Metric::Sensor(id)),
+            _ => Err(format!("métrica desconhecida: {}", text)),
+        }
+    }
+
+    pub fn unit(&self, performance: &Performance) -> &'static str {
+        match self {
+            Metric::Sensor(id) => performance.sensors.iter().find(|sensor| sensor.id.eq(id)).map(|sensor| sensor.kind.unit()).unwrap_or(""),
+            Metric::Cpu | Metric::Core(_) | Metric::ProcessCpu(_) => "%",
+            Metric::Memory | Metric::Swap | Metric::DiskFree(_) => "GiB",
+            Metric::Network(_) => "B/s",
+            Metric::ProcessMemory(_) => "MB",
+        }
+    }
+
+    fn value(&self, performance: &Performance, procs: &[MyProcess], network_rates: &HashMap<String, f64>) -> Option<f64> {
+        let last_use = |uses: &Vec<f32>| uses.last().map(|usage| *usage as f64);
+        match self {
+            Metric::Cpu => {
+                let uses = performance.cpus.iter().filter_map(|cpu| last_use(&cpu.uses)).collect::<Vec<_>>();
+                match uses.is_empty() {
+                    true => None,
+                    false => Some(uses.iter().sum::<f64>() / uses.len() as f64),
+                }
+            }
+            Metric::Core(name) => performance.cpus.iter().find(|cpu| cpu.name.eq(name)).and_then(|cpu| last_use(&cpu.uses)),
+            Metric::Memory => Some(performance.mem.used as f64),
+            Metric::Swap => Some(performance.swap.used as f64),
+            Metric::DiskFree(mount) => performance.disks.iter().find(|disk| disk.local.eq(mount)).map(|disk| disk.free as f64 / (1u64 << 30) as f64),
+            Metric::Network(interface) => network_rates.get(interface).copied(),
+            // The busiest process with that name, so one runaway instance is enough to trigger.
+            Metric::ProcessCpu(name) => procs.iter().filter(|proc| proc.name.eq(name)).map(|proc| proc.cpu_usage as f64).reduce(f64::max),
+            Metric::ProcessMemory(name) => procs.iter().filter(|proc| proc.name.eq(name)).map(|proc| proc.memory as f64).reduce(f64::max),
+            Metric::Sensor(id) => performance.sensors.iter().find(|sensor| sensor.id.eq(id)).map(|sensor| sensor.value),
+        }
+    }
+}
+
+impl AlertRule {
+    fn breached(&self, value: f64) -> bool {
+        match self.above {
+            true => value > self.threshold,
+            false => value < self.threshold,
+        }
+    }
+
+    // Only clears once the value is back past the threshold by the hysteresis margin.
+    fn cleared(&self, value: f64) -> bool {
+        match self.above {
+            true => value <= self.threshold - self.hysteresis,
+            false => value >= self.threshold + self.hysteresis,
+        }
+    }
+}
+
+impl AlertEvent {
+    pub fn reading(&self) -> String {
+        match self.value {
+            Some(value) => format!("{:.1} {}", value, self.unit),
+            None => "sem leitura".to_string(),
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self.kind {
+            AlertKind::Fired => format!("Alerta: {} (atual: {})", self.rule, self.reading()),
+            AlertKind::Resolved => format!("Normalizado: {} (atual: {})", self.rule, self.reading()),
+        }
+    }
+}
+
+impl Notifier for DesktopNotifier {
+    fn notify(&mut self, event: &AlertEvent) {
+        let summary = match event.kind {
+            AlertKind::Fired => "Task manager: alerta",
+            AlertKind::Resolved => "Task manager: normalizado",
+        };
+        let body = event.message();
+        std::thread::spawn(move || {
+            let sent = Command::new("gdbus")
+                .args([
+                    "call", "--session",
+                    "--dest", "org.freedesktop.Notifications",
+                    "--object-path", "/org/freedesktop/Notifications",
+                    "--method", "org.freedesktop.Notifications.Notify",
+                    "task-manager", "0", "", summary, &body, "[]", "{}", "5000",
+                ])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+            if !matches!(sent, Ok(status) if status.success()) {
+                let _ = Command::new("notify-send").args([summary, &body]).stderr(Stdio::null()).status();
+            }
+        });
+    }
+}
+
+// "<metric> <op> <threshold> [for <duration>] [hysteresis <margin>]", e.g.
+// "cpu > 90 for 30s hysteresis 10" or "disk:/ < 5".
+pub fn parse_alert_rule(text: &str) -> Result<AlertRule, String> {
+    let line = text.trim();
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let [metric, operator, threshold, rest @ ..] = words.as_slice() else {
+        return Err("esperado: <métrica> <op> <limite> [for <duração>] [hysteresis <margem>]".to_string());
+    };
+    let above = match *operator {
+        ">" => true,
+        "<" => false,
+        _ => return Err(format!("operador inválido: {} (use > ou <)", operator)),
+    };
+    let threshold = threshold.parse::<f64>().map_err(|_| format!("limite inválido: {}", threshold))?;
+    let mut rule = AlertRule { text: line.to_string(), metric: Metric::parse(metric)?, above, threshold, sustain: 0, hysteresis: 0.0 };
+    for option in rest.chunks(2) {
+        match option {
+            ["for", duration] => rule.sustain = parse_duration(duration)?,
+            ["hysteresis", margin] => rule.hysteresis = margin.parse::<f64>().map_err(|_| format!("histerese inválida: {}", margin))?,
+            _ => return Err(format!("opção desconhecida: {}", option.join(" "))),
+        }
+    }
+    Ok(rule)
+}
+
+// One rule per sensor that reports a critical value, firing within margin_percent of it
+// and clearing again once it's twice that far away.
+pub fn sensor_alert_rules(sensors: &[Sensor], margin_percent: f64) -> Vec<AlertRule> {
+    sensors
+        .iter()
+        .filter_map(|sensor| {
+            let critical = sensor.critical.filter(|critical| *critical > 0.0)?;
+            let margin = critical * margin_percent / 100.0;
+            let threshold = critical - margin;
+            Some(AlertRule {
+                text: format!("sensor:{} > {:.1} (crítico {:.1} {})", sensor.id, threshold, critical, sensor.kind.unit()),
+                metric: Metric::Sensor(sensor.id.clone()),
+                above: true,
+                threshold,
+                sustain: 0,
+                hysteresis: margin,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_alert_rules(lines: &[String]) -> (Vec<AlertRule>, Vec<String>) {
+    let mut rules = Vec::new();
+    let mut errors = Vec::new();
+    for line in lines.iter().filter(|line| !line.trim().is_empty() && !line.trim().starts_with('#')) {
+        match parse_alert_rule(line) {
+            Ok(rule) => rules.push(rule),
+            Err(err) => errors.push(format!("{}: {}", line.trim(), err)),
+        }
+    }
+    (rules, errors)
+}
+
+impl AlertMonitor {
+    pub fn evaluate(&mut self, rules: &[AlertRule], performance: &Performance, procs: &[MyProcess], now: u64, notifiers: &mut [Box<dyn Notifier>]) -> Vec<AlertEvent> {
+        let network_rates = self.network_rates(performance, now);
+        let mut events = Vec::new();
+        for rule in rules {
+            let value = rule.metric.value(performance, procs, &network_rates);
+            let state = self.states.entry(rule.text.clone()).or_insert(RuleState { since: None, active: false });
+            let kind = match (state.active, value) {
+                // Without a reading the condition can't be said to hold, so a firing alert is resolved.
+                (true, None) => Some(AlertKind::Resolved),
+                (false, None) => {
+                    state.since = None;
+                    None
+                }
+                (false, Some(value)) if rule.breached(value) => {
+                    let since = *state.since.get_or_insert(now);
+                    match now.saturating_sub(since) >= rule.sustain {
+                        true => Some(AlertKind::Fired),
+                        false => None,
+                    }
+                }
+                (false, Some(_)) => {
+                    state.since = None;
+                    None
+                }
+                (true, Some(value)) if rule.cleared(value) => Some(AlertKind::Resolved),
+                (true, Some(_)) => None,
+            };
+            if let Some(kind) = kind {
+                state.active = kind == AlertKind::Fired;
+                state.since = None;
+                events.push(AlertEvent { kind, timestamp: now, rule: rule.text.clone(), value, unit: rule.metric.unit(performance) });
+            }
+        }
+        self.states.retain(|text, _| rules.iter().any(|rule| rule.text.eq(text)));
+        for event in events.iter() {
+            for notifier in notifiers.iter_mut() {
+                notifier.notify(event);
+            }
+        }
+        events
+    }
+
+    // Rates come from the cumulative counters, so they don't depend on how sysinfo was refreshed.
+    fn network_rates(&mut self, performance: &Performance, now: u64) -> HashMap<String, f64> {
+        let mut rates = HashMap::new();
+        for network in performance.networks.iter() {
+            let total = network.total_received + network.total_transmitted;
+            match self.network_totals.get(&network.name) {
+                Some((previous, at)) if now > *at => {
+                    rates.insert(network.name.clone(), total.saturating_sub(*previous) as f64 / (now - at) as f64);
+                }
+                Some(_) => continue,
+                None => {}
+            }
+            self.network_totals.insert(network.name.clone(), (total, now));
+        }
+        rates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::utils::app_props::{ MyCpu, MyDisk };
+
+    #[derive(Clone, Default)]
+    struct RecordingNotifier {
+        events: Rc<RefCell<Vec<AlertEvent>>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&mut self, event: &AlertEvent) {
+            self.events.borrow_mut().push(event.clone());
+        }
+    }
+
+    fn with_memory(used: u64) -> Performance {
+        let mut performance = Performance::default();
+        performance.mem.used = used;
+        performance
+    }
+
+    fn with_core(usage: Option<f32>) -> Performance {
+        let mut performance = Performance::default();
+        if let Some(usage) = usage {
+            performance.cpus.push(MyCpu { name: "cpu0".to_string(), uses: vec![usage], breakdown: Vec::new(), frequencies: Vec::new() });
+        }
+        performance
+    }
+
+    // Feeds one reading per second and returns the kinds of the events each one produced.
+    fn run(rule: &str, readings: &[Performance]) -> Vec<Vec<AlertKind>> {
+        let rules = vec![parse_alert_rule(rule).unwrap()];
+        let mut monitor = AlertMonitor::default();
+        readings
+            .iter()
+            .enumerate()
+            .map(|(second, performance)| {
+                monitor.evaluate(&rules, performance, &[], second as u64, &mut []).iter().map(|event| event.kind).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_rules_and_reports_bad_lines() {
+        let lines = ["cpu > 90 for 30s hysteresis 10", "# comentário", "", "disk:/ < 5", "mem >> 3", "gpu > 1", "cpu > 90 for 1x"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let (rules, errors) = parse_alert_rules(&lines);
+        assert_eq!(rules.len(), 2);
+        assert!(rules[0].metric == Metric::Cpu && rules[0].above && rules[0].threshold == 90.0);
+        assert_eq!((rules[0].sustain, rules[0].hysteresis), (30, 10.0));
+        assert!(rules[1].metric == Metric::DiskFree("/".to_string()) && !rules[1].above);
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn fires_only_after_the_sustain_period() {
+        // The dip at 2s restarts the count, so it takes until 5s.
+        let readings = [with_memory(10), with_memory(10), with_memory(1), with_memory(10), with_memory(10), with_memory(10), with_memory(10)];
+        let kinds = run("mem > 5 for 2s", &readings);
+        assert_eq!(kinds.iter().position(|kinds| kinds.contains(&AlertKind::Fired)), Some(5));
+        assert_eq!(kinds.iter().flatten().count(), 1);
+    }
+
+    #[test]
+    fn resolves_only_past_the_hysteresis_margin() {
+        let readings = [with_memory(10), with_memory(4), with_memory(3), with_memory(10)];
+        let kinds = run("mem > 5 hysteresis 2", &readings);
+        assert!(kinds == vec![vec![AlertKind::Fired], vec![], vec![AlertKind::Resolved], vec![AlertKind::Fired]]);
+    }
+
+    #[test]
+    fn resolves_when_the_metric_disappears() {
+        let readings = [with_core(Some(95.0)), with_core(None), with_core(None), with_core(Some(95.0))];
+        let kinds = run("cpu:cpu0 > 90", &readings);
+        assert!(kinds == vec![vec![AlertKind::Fired], vec![AlertKind::Resolved], vec![], vec![AlertKind::Fired]]);
+    }
+
+    #[test]
+    fn disk_free_is_in_gib() {
+        let mut performance = Performance::default();
+        let disk = MyDisk { local: "/".to_string(), kind: String::new(), structure: String::new(), space: 0, removable: false, used: 0, free: 10 << 30 };
+        performance.disks.push(disk);
+        let metric = parse_alert_rule("disk:/ < 10").unwrap().metric;
+        assert_eq!(metric.value(&performance, &[], &HashMap::new()), Some(10.0));
+        assert_eq!(metric.unit(&performance), "GiB");
+    }
+
+    #[test]
+    fn notifies_every_event() {
+        let rules = vec![parse_alert_rule("mem > 5").unwrap()];
+        let mut monitor = AlertMonitor::default();
+        let recorder = RecordingNotifier::default();
+        let mut notifiers: Vec<Box<dyn Notifier>> = vec![Box::new(recorder.clone())];
+        for (second, used) in [10, 1, 10].iter().enumerate() {
+            monitor.evaluate(&rules, &with_memory(*used), &[], second as u64, &mut notifiers);
+        }
+        let events = recorder.events.borrow();
+        assert!(events.iter().map(|event| event.kind).collect::<Vec<_>>() == vec![AlertKind::Fired, AlertKind::Resolved, AlertKind::Fired]);
+        assert_eq!(events[0].message(), "Alerta: mem > 5 (atual: 10.0 GiB)");
+    }
+}
\ No newline at end of file
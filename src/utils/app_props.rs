@@ -1,13 +1,24 @@
-use futures_channel::mpsc::UnboundedReceiver;
+use futures_channel::mpsc::{ UnboundedReceiver, UnboundedSender };
 use std::cell::Cell;
 use std::cmp::Ordering;
 
+use super::namespaces::Namespaces;
+use super::events::ProcessEvent;
+use super::launcher::LaunchUpdate;
+use super::cpustat::CpuBreakdown;
+use super::sensors::Sensor;
+use super::power::PowerStatus;
+use super::rapl::RaplPower;
+
 pub struct AppProps {
     pub receiver_procs: Cell<Option<UnboundedReceiver<Vec<MyProcess>>>>,
     pub receiver_performance: Cell<Option<UnboundedReceiver<Performance>>>,
+    pub receiver_events: Cell<Option<UnboundedReceiver<Vec<ProcessEvent>>>>,
+    pub receiver_launches: Cell<Option<UnboundedReceiver<LaunchUpdate>>>,
+    pub sender_launches: UnboundedSender<LaunchUpdate>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct MyProcess {
     pub pid: u32,
     pub name: String,
@@ -15,6 +26,16 @@ pub struct MyProcess {
     pub cpu_usage: f32,
     pub read_bytes: u64,
     pub written_bytes: u64,
+    pub namespaces: Namespaces,
+    pub parent: Option<u32>,
+    pub cgroup: String,
+    pub frozen: bool,
+    pub oom_score: i32,
+    pub oom_score_adj: i32,
+    pub start_time: u64,
+    pub cmd: String,
+    pub state: char,
+    pub wchan: String,
 }
 
 impl MyProcess {
@@ -28,6 +49,18 @@ impl MyProcess {
         if field.eq("memory") {
             return self.memory.cmp(&other.memory);
         }
+        if field.eq("state") {
+            return self.state.cmp(&other.state);
+        }
+        if field.eq("oom_score") {
+            return self.oom_score.cmp(&other.oom_score);
+        }
+        if field.eq("oom_score_adj") {
+            return self.oom_score_adj.cmp(&other.oom_score_adj);
+        }
+        if let Some(kind) = field.strip_prefix("ns:") {
+            return self.namespaces.get(kind).cmp(&other.namespaces.get(kind));
+        }
         return self.cpu_usage.total_cmp(&other.cpu_usage);
     }
     pub fn new(other: &MyProcess) -> MyProcess {
@@ -39,28 +72,44 @@ impl MyProcess {
             memory: other.memory,
             cpu_usage: other.cpu_usage,
             read_bytes: other.read_bytes,
-            written_bytes: other.written_bytes
+            written_bytes: other.written_bytes,
+            namespaces: other.namespaces.clone(),
+            parent: other.parent,
+            cgroup: other.cgroup.clone(),
+            frozen: other.frozen,
+            oom_score: other.oom_score,
+            oom_score_adj: other.oom_score_adj,
+            start_time: other.start_time,
+            cmd: other.cmd.clone(),
+            state: other.state,
+            wchan: other.wchan.clone(),
         }
     }
 }
 
 impl Performance {
     pub fn default() -> Performance {
-        Performance { cpus: Vec::new(), mem: Mem::default(), swap: Swap::default(), networks: Vec::new(), disks: Vec::new() }
+        Performance { cpus: Vec::new(), total_breakdown: Vec::new(), mem: Mem::default(), swap: Swap::default(), networks: Vec::new(), disks: Vec::new(), sensors: Vec::new(), power: PowerStatus::default(), energy: Vec::new() }
     }
 }
 pub struct Performance {
     pub cpus: Vec<MyCpu>,
+    pub total_breakdown: Vec<CpuBreakdown>,
     pub mem: Mem,
     pub swap: Swap,
     pub networks: Vec<Network>,
     pub disks: Vec<MyDisk>,
+    pub sensors: Vec<Sensor>,
+    pub power: PowerStatus,
+    pub energy: Vec<RaplPower>,
 }
 
 #[derive(Clone)]
 pub struct MyCpu {
     pub name: String,
     pub uses: Vec<f32>, 
+    pub breakdown: Vec<CpuBreakdown>,
+    pub frequencies: Vec<u64>,
 }
 
 impl Mem {
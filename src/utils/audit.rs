@@ -0,0 +1,133 @@
+use std::fmt::Display;
+use std::fs::{ self, OpenOptions };
+use std::io::{ self, Write };
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{ Deserialize, Serialize };
+
+use super::functions::now_secs;
+use super::proc_connector::{ read_cmdline, read_comm };
+
+// Each file is rotated past this size, keeping audit.log.1 .. audit.log.N.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+const ROTATED_FILES: usize = 5;
+
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub user: String,
+    pub pid: u32,
+    pub name: String,
+    pub cmdline: String,
+    pub action: String,
+    pub parameters: String,
+    pub outcome: String,
+}
+
+// Read before acting, since a killed process takes its /proc entry with it.
+pub struct AuditTarget {
+    pub pid: u32,
+    pub name: String,
+    pub cmdline: String,
+}
+
+impl AuditTarget {
+    pub fn capture(pid: u32) -> AuditTarget {
+        AuditTarget {
+            pid,
+            name: read_comm(pid).unwrap_or_else(|| "?".to_string()),
+            cmdline: read_cmdline(pid).unwrap_or_default(),
+        }
+    }
+
+    pub fn record(&self, action: &str, parameters: &str, outcome: &str) {
+        let entry = AuditEntry {
+            timestamp: now_secs(),
+            user: current_user(),
+            pid: self.pid,
+            name: self.name.clone(),
+            cmdline: self.cmdline.clone(),
+            action: action.to_string(),
+            parameters: parameters.to_string(),
+            outcome: outcome.to_string(),
+        };
+        if let Err(err) = append(&entry) {
+            eprintln!("Não foi possível gravar o log de auditoria: {}", err);
+        }
+    }
+}
+
+impl AuditEntry {
+    pub fn matches(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        text.is_empty()
+            || self.pid.to_string().eq(&text)
+            || [&self.user, &self.name, &self.cmdline, &self.action, &self.parameters, &self.outcome]
+                .iter()
+                .any(|field| field.to_lowercase().contains(&text))
+    }
+}
+
+pub fn audited<T, E: Display>(pid: u32, action: &str, parameters: &str, perform: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let target = AuditTarget::capture(pid);
+    let result = perform();
+    match &result {
+        Ok(_) => target.record(action, parameters, "ok"),
+        Err(err) => target.record(action, parameters, &format!("erro: {}", err)),
+    }
+    result
+}
+
+pub fn log_dir() -> PathBuf {
+    match std::env::var_os("XDG_STATE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("task-manager"),
+        _ => PathBuf::from(std::env::var_os("HOME").unwrap_or_default()).join(".local").join("state").join("task-manager"),
+    }
+}
+
+fn log_path(index: usize) -> PathBuf {
+    match index {
+        0 => log_dir().join("audit.log"),
+        index => log_dir().join(format!("audit.log.{}", index)),
+    }
+}
+
+fn current_user() -> String {
+    let uid = unsafe { libc::getuid() };
+    match std::env::var("USER") {
+        Ok(user) if !user.is_empty() => format!("{} ({})", user, uid),
+        _ => uid.to_string(),
+    }
+}
+
+fn rotate() -> io::Result<()> {
+    for index in (1..ROTATED_FILES).rev() {
+        if log_path(index).exists() {
+            fs::rename(log_path(index), log_path(index + 1))?;
+        }
+    }
+    fs::rename(log_path(0), log_path(1))
+}
+
+fn append(entry: &AuditEntry) -> io::Result<()> {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    fs::create_dir_all(log_dir())?;
+    if fs::metadata(log_path(0)).map(|metadata| metadata.len() >= MAX_LOG_BYTES).unwrap_or(false) {
+        rotate()?;
+    }
+    let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(log_path(0))?;
+    writeln!(file, "{}", line)
+}
+
+// Oldest first, across the rotated files.
+pub fn read_audit_log() -> Vec<AuditEntry> {
+    (0..=ROTATED_FILES)
+        .rev()
+        .filter_map(|index| fs::read_to_string(log_path(index)).ok())
+        .flat_map(|content| content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect::<Vec<AuditEntry>>())
+        .collect()
+}
\ No newline at end of file
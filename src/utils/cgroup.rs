@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+use serde::{ Deserialize, Serialize };
+
+use super::audit::log_dir;
+use super::functions::{ read_number, read_trimmed };
+use super::helper::perform;
+use super::helper_protocol::{ read_start_time, Request };
+use super::protection::{ authorize, Origin };
+
+pub use super::helper_protocol::{ process_cgroup, CGROUP_ROOT, MANAGED_GROUP };
+const CONTROLLERS: [&str; 3] = ["cpu", "memory", "io"];
+const CPU_PERIOD: u64 = 100000;
+// The kernel refuses a cpu.max quota below 1 ms per period.
+const MIN_CPU_QUOTA: u64 = 1000;
+// Kept next to the audit log, so a thaw after restarting the app still knows where to put things back.
+const FROZEN_FILE: &str = "frozen.json";
+
+#[derive(Clone, Default, PartialEq)]
+pub struct CgroupLimits {
+    pub cpu_max: String,
+    pub memory_max: String,
+    pub memory_high: String,
+    pub io_max: String,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct CgroupStats {
+    pub nr_periods: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+    pub memory_current: u64,
+    pub memory_high_events: u64,
+    pub memory_max_events: u64,
+    pub oom_kills: u64,
+}
+
+// A process moved into one of our freeze groups, and the cgroup it came from.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+struct FrozenMember {
+    pid: u32,
+    start_time: u64,
+    cgroup: String,
+}
+
+impl CgroupLimits {
+    pub fn read(dir: &Path) -> CgroupLimits {
+        CgroupLimits {
+            cpu_max: read_trimmed(&dir.join("cpu.max")).unwrap_or_default(),
+            memory_max: read_trimmed(&dir.join("memory.max")).unwrap_or_default(),
+            memory_high: read_trimmed(&dir.join("memory.high")).unwrap_or_default(),
+            io_max: read_trimmed(&dir.join("io.max")).unwrap_or_default(),
+        }
+    }
+
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        fs::write(dir.join("cpu.max"), &self.cpu_max)?;
+        fs::write(dir.join("memory.max"), &self.memory_max)?;
+        fs::write(dir.join("memory.high"), &self.memory_high)?;
+        for line in self.io_max.lines().filter(|line| !line.trim().is_empty()) {
+            fs::write(dir.join("io.max"), line.trim())?;
+        }
+        Ok(())
+    }
+}
+
+impl CgroupStats {
+    pub fn read(dir: &Path) -> CgroupStats {
+        let cpu_stat = read_keyed(&dir.join("cpu.stat"));
+        let memory_events = read_keyed(&dir.join("memory.events"));
+        CgroupStats {
+            nr_periods: keyed_value(&cpu_stat, "nr_periods"),
+            nr_throttled: keyed_value(&cpu_stat, "nr_throttled"),
+            throttled_usec: keyed_value(&cpu_stat, "throttled_usec"),
+            memory_current: read_number(&dir.join("memory.current")).unwrap_or(0),
+            memory_high_events: keyed_value(&memory_events, "high"),
+            memory_max_events: keyed_value(&memory_events, "max"),
+            oom_kills: keyed_value(&memory_events, "oom_kill"),
+        }
+    }
+}
+
+pub fn cgroup_dir(cgroup: &str) -> PathBuf {
+    Path::new(CGROUP_ROOT).join(cgroup.trim_start_matches('/'))
+}
+
+pub fn managed_dir(name: &str) -> PathBuf {
+    Path::new(CGROUP_ROOT).join(MANAGED_GROUP).join(name)
+}
+
+pub fn create_managed_group(name: &str) -> io::Result<PathBuf> {
+    let base = Path::new(CGROUP_ROOT).join(MANAGED_GROUP);
+    fs::create_dir_all(&base)?;
+    enable_controllers(Path::new(CGROUP_ROOT))?;
+    enable_controllers(&base)?;
+    let dir = managed_dir(name);
+    if !dir.exists() {
+        fs::create_dir(&dir)?;
+    }
+    Ok(dir)
+}
+
+pub fn move_to_managed_group(pid: u32, origin: Origin) -> io::Result<PathBuf> {
+    authorize(pid, origin).map_err(io::Error::other)?;
+    let dir = create_managed_group(&format!("pid-{}", pid))?;
+    move_process(pid, &dir, origin)?;
+    Ok(dir)
+}
+
+pub fn move_process(pid: u32, dir: &Path, origin: Origin) -> io::Result<()> {
+    let cgroup = dir.strip_prefix(CGROUP_ROOT).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "cgroup fora de /sys/fs/cgroup"))?;
+    perform(Request::Cgroup { pid, cgroup: cgroup.to_string_lossy().to_string() }, origin).map_err(io::Error::other)
+}
+
+pub fn is_frozen(dir: &Path) -> bool {
+    read_keyed(&dir.join("cgroup.events")).iter().any(|(key, value)| key.eq("frozen") && *value == 1)
+}
+
+pub fn set_frozen(dir: &Path, frozen: bool) -> io::Result<()> {
+    fs::write(dir.join("cgroup.freeze"), if frozen { "1" } else { "0" })
+}
+
+// Every process in the group and in the groups below it, which is what cgroup.freeze reaches.
+pub fn cgroup_members(dir: &Path) -> Vec<u32> {
+    let mut members = read_trimmed(&dir.join("cgroup.procs")).unwrap_or_default().lines().filter_map(|pid| pid.parse().ok()).collect::<Vec<u32>>();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten().filter(|entry| entry.path().is_dir()) {
+            members.extend(cgroup_members(&entry.path()));
+        }
+    }
+    members
+}
+
+fn frozen_path() -> PathBuf {
+    log_dir().join(FROZEN_FILE)
+}
+
+fn load_frozen() -> HashMap<String, Vec<FrozenMember>> {
+    fs::read_to_string(frozen_path()).ok().and_then(|content| serde_json::from_str(&content).ok()).unwrap_or_default()
+}
+
+fn save_frozen(groups: &HashMap<String, Vec<FrozenMember>>) -> io::Result<()> {
+    fs::create_dir_all(log_dir())?;
+    fs::write(frozen_path(), serde_json::to_string_pretty(groups).map_err(io::Error::other)?)
+}
+
+fn relative_cgroup(dir: &Path) -> String {
+    format!("/{}", dir.strip_prefix(CGROUP_ROOT).unwrap_or(dir).display())
+}
+
+// Back to where they came from; anything forked after the thaw follows the first process.
+fn restore_members(dir: &Path, members: &[FrozenMember], origin: Origin) -> io::Result<()> {
+    let mut errors = Vec::new();
+    for member in members.iter().filter(|member| read_start_time(member.pid) == Some(member.start_time)) {
+        if let Err(err) = move_process(member.pid, &cgroup_dir(&member.cgroup), origin) {
+            errors.push(format!("{}: {}", member.pid, err));
+        }
+    }
+    if let Some(first) = members.first() {
+        for pid in cgroup_members(dir) {
+            if let Err(err) = move_process(pid, &cgroup_dir(&first.cgroup), origin) {
+                errors.push(format!("{}: {}", pid, err));
+            }
+        }
+    }
+    let _ = fs::remove_dir(dir);
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(io::Error::other(format!("não foi possível devolver ao cgroup de origem: {}", errors.join("; ")))),
+    }
+}
+
+// When the processes already have a group of their own (a service, a scope) that group is frozen
+// where it is. Otherwise they are moved into a group of ours, remembering where each came from so
+// that thawing can put them back and systemd keeps accounting for them.
+pub fn freeze_processes(pid: u32, others: &[u32], origin: Origin) -> io::Result<PathBuf> {
+    let mut targets = others.to_vec();
+    targets.push(pid);
+    for target in targets.iter() {
+        authorize(*target, origin).map_err(io::Error::other)?;
+    }
+    let cgroup = process_cgroup(pid).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "cgroup v2 não encontrado"))?;
+    let current = cgroup_dir(&cgroup);
+    let owns_group = !is_shared_group(&cgroup)
+        && targets.iter().all(|target| process_cgroup(*target).as_deref() == Some(cgroup.as_str()))
+        && cgroup_members(&current).iter().all(|member| targets.contains(member));
+    if owns_group {
+        set_frozen(&current, true)?;
+        return Ok(current);
+    }
+
+    let dir = create_managed_group(&format!("freeze-{}-{}", pid, read_start_time(pid).unwrap_or(0)))?;
+    let mut members = Vec::new();
+    for target in targets.iter() {
+        let (Some(original), Some(start_time)) = (process_cgroup(*target), read_start_time(*target)) else {
+            continue;
+        };
+        if let Err(err) = move_process(*target, &dir, origin) {
+            let _ = restore_members(&dir, &members, origin);
+            return Err(err);
+        }
+        members.push(FrozenMember { pid: *target, start_time, cgroup: original });
+    }
+    let mut groups = load_frozen();
+    groups.insert(relative_cgroup(&dir), members);
+    save_frozen(&groups)?;
+    set_frozen(&dir, true)?;
+    Ok(dir)
+}
+
+// The root, slices and login sessions hold unrelated processes (often the whole desktop).
+fn is_shared_group(cgroup: &str) -> bool {
+    let name = cgroup.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+    name.is_empty() || name.ends_with(".slice") || name.starts_with("user@") || (name.starts_with("session-") && name.ends_with(".scope"))
+}
+
+pub fn set_process_cgroup_frozen(pid: u32, frozen: bool, origin: Origin) -> io::Result<PathBuf> {
+    authorize(pid, origin).map_err(io::Error::other)?;
+    let cgroup = process_cgroup(pid).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "cgroup v2 não encontrado"))?;
+    if cgroup.trim_start_matches('/').is_empty() || (frozen && is_shared_group(&cgroup)) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} é compartilhado (raiz, slice ou sessão) e não pode ser congelado", cgroup)));
+    }
+    let dir = cgroup_dir(&cgroup);
+    // Freezing reaches every process below the group, so each one has to pass the same check.
+    if frozen {
+        for member in cgroup_members(&dir) {
+            authorize(member, origin).map_err(io::Error::other)?;
+        }
+    }
+    set_frozen(&dir, frozen)?;
+    if !frozen {
+        let mut groups = load_frozen();
+        if let Some(members) = groups.remove(&cgroup) {
+            save_frozen(&groups)?;
+            restore_members(&dir, &members, origin)?;
+        }
+    }
+    Ok(dir)
+}
+
+fn enable_controllers(dir: &Path) -> io::Result<()> {
+    let enabled = read_trimmed(&dir.join("cgroup.subtree_control")).unwrap_or_default();
+    let missing = CONTROLLERS
+        .iter()
+        .filter(|controller| !enabled.split_whitespace().any(|c| c.eq(**controller)))
+        .map(|controller| format!("+{}", controller))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    fs::write(dir.join("cgroup.subtree_control"), missing.join(" "))
+}
+
+// An empty field means "no limit", which cgroup v2 spells as "max".
+pub fn cpu_max_from_percent(percent: &str) -> Result<String, String> {
+    let percent = percent.trim();
+    if percent.is_empty() {
+        return Ok(format!("max {}", CPU_PERIOD));
+    }
+    let value = percent.parse::<f64>().map_err(|_| format!("CPU inválida: {}", percent))?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("CPU inválida: {}", percent));
+    }
+    let quota = (value / 100.0 * CPU_PERIOD as f64) as u64;
+    if quota < MIN_CPU_QUOTA {
+        return Err(format!("CPU mínima é {}%", MIN_CPU_QUOTA * 100 / CPU_PERIOD));
+    }
+    Ok(format!("{} {}", quota, CPU_PERIOD))
+}
+
+pub fn bytes_from_mib(mib: &str) -> Result<String, String> {
+    let mib = mib.trim();
+    if mib.is_empty() {
+        return Ok("max".to_string());
+    }
+    let value = mib.parse::<u64>().map_err(|_| format!("Memória inválida: {}", mib))?;
+    value.checked_mul(1024 * 1024).map(|bytes| bytes.to_string()).ok_or_else(|| format!("Memória grande demais: {} MiB", mib))
+}
+
+fn read_keyed(path: &Path) -> Vec<(String, u64)> {
+    read_trimmed(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+fn keyed_value(values: &[(String, u64)], key: &str) -> u64 {
+    values.iter().find(|(k, _)| k.eq(key)).map(|(_, v)| *v).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_mib_to_bytes() {
+        assert_eq!(bytes_from_mib(" 512 "), Ok("536870912".to_string()));
+        assert_eq!(bytes_from_mib(""), Ok("max".to_string()));
+        assert!(bytes_from_mib("-1").is_err());
+        assert!(bytes_from_mib("1.5").is_err());
+    }
+
+    #[test]
+    fn refuses_mib_that_overflow() {
+        assert_eq!(bytes_from_mib(&(u64::MAX >> 20).to_string()), Ok(((u64::MAX >> 20) << 20).to_string()));
+        assert!(bytes_from_mib(&((u64::MAX >> 20) + 1).to_string()).is_err());
+        assert!(bytes_from_mib(&u64::MAX.to_string()).is_err());
+    }
+
+    #[test]
+    fn converts_cpu_percent_to_quota() {
+        assert_eq!(cpu_max_from_percent("50"), Ok("50000 100000".to_string()));
+        assert_eq!(cpu_max_from_percent("250"), Ok("250000 100000".to_string()));
+        assert_eq!(cpu_max_from_percent(""), Ok("max 100000".to_string()));
+        assert_eq!(cpu_max_from_percent("1"), Ok("1000 100000".to_string()));
+        for percent in ["0", "-5", "0.5", "NaN", "inf", "-inf", "metade"] {
+            assert!(cpu_max_from_percent(percent).is_err(), "{}", percent);
+        }
+    }
+
+    #[test]
+    fn recognizes_shared_groups() {
+        for cgroup in ["/", "/user.slice", "/user.slice/user-1000.slice/user@1000.service", "/user.slice/user-1000.slice/session-2.scope"] {
+            assert!(is_shared_group(cgroup), "{}", cgroup);
+        }
+        for cgroup in ["/system.slice/nginx.service", "/task-manager/freeze-42-100", "/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope"] {
+            assert!(!is_shared_group(cgroup), "{}", cgroup);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use super::functions::{ read_number, read_trimmed };
+
+const SYS_CPU: &str = "/sys/devices/system/cpu";
+
+#[derive(Clone, PartialEq)]
+pub struct CoreInfo {
+    pub name: String,
+    pub min_khz: Option<u64>,
+    pub max_khz: Option<u64>,
+    pub governor: String,
+    pub package: Option<u32>,
+    pub core: Option<u32>,
+    pub siblings: String,
+    pub numa_node: Option<u32>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CpuInfo {
+    pub model: String,
+    pub caches: Vec<String>,
+    pub cores: Vec<CoreInfo>,
+}
+
+// x86 has "model name"; ARM kernels use "Processor", "Hardware" or only "CPU part".
+fn read_model() -> String {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    ["model name", "Processor", "Hardware", "cpu model", "CPU part"]
+        .iter()
+        .find_map(|key| {
+            cpuinfo.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                match name.trim().eq(*key) {
+                    true => Some(value.trim().to_string()),
+                    false => None,
+                }
+            })
+        })
+        .unwrap_or_else(|| "Desconhecido".to_string())
+}
+
+// One entry per cache level/type as seen by cpu0, e.g. "L1 Data: 32K".
+fn read_caches() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(Path::new(SYS_CPU).join("cpu0").join("cache")) else {
+        return Vec::new();
+    };
+    let mut caches = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.file_name().map(|name| name.to_string_lossy().starts_with("index")).unwrap_or(false))
+        .filter_map(|path| {
+            let level = read_trimmed(&path.join("level"))?;
+            let kind = read_trimmed(&path.join("type")).unwrap_or_default();
+            let size = read_trimmed(&path.join("size"))?;
+            let shared = read_trimmed(&path.join("shared_cpu_list")).unwrap_or_default();
+            Some(format!("L{} {}: {} (CPUs {})", level, kind, size, shared))
+        })
+        .collect::<Vec<_>>();
+    caches.sort();
+    caches
+}
+
+fn numa_node(dir: &Path) -> Option<u32> {
+    fs::read_dir(dir).ok()?.flatten().find_map(|entry| entry.file_name().to_string_lossy().strip_prefix("node")?.parse().ok())
+}
+
+fn read_core(name: &str) -> CoreInfo {
+    let dir = Path::new(SYS_CPU).join(name);
+    let cpufreq = dir.join("cpufreq");
+    let topology = dir.join("topology");
+    CoreInfo {
+        name: name.to_string(),
+        min_khz: read_number(&cpufreq.join("scaling_min_freq")).or_else(|| read_number(&cpufreq.join("cpuinfo_min_freq"))),
+        max_khz: read_number(&cpufreq.join("scaling_max_freq")).or_else(|| read_number(&cpufreq.join("cpuinfo_max_freq"))),
+        governor: read_trimmed(&cpufreq.join("scaling_governor")).unwrap_or_else(|| "-".to_string()),
+        package: read_number(&topology.join("physical_package_id")),
+        core: read_number(&topology.join("core_id")),
+        siblings: read_trimmed(&topology.join("thread_siblings_list")).unwrap_or_default(),
+        numa_node: numa_node(&dir),
+    }
+}
+
+pub fn read_cpu_info() -> CpuInfo {
+    let mut names = fs::read_dir(SYS_CPU)
+        .map(|entries| entries.flatten().map(|entry| entry.file_name().to_string_lossy().to_string()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    names.retain(|name| name.strip_prefix("cpu").map(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).unwrap_or(false));
+    names.sort_by_key(|name| name[3..].parse::<u32>().unwrap_or(0));
+    CpuInfo {
+        model: read_model(),
+        caches: read_caches(),
+        cores: names.iter().map(|name| read_core(name)).collect(),
+    }
+}
+
+// scaling_cur_freq is what the governor asked for; it's the value tools like cpupower show.
+pub fn read_frequency_mhz(name: &str) -> Option<u64> {
+    let cpufreq: PathBuf = Path::new(SYS_CPU).join(name).join("cpufreq");
+    read_number::<u64>(&cpufreq.join("scaling_cur_freq")).map(|khz| khz / 1000)
+}
+
+impl CpuInfo {
+    pub fn sockets(&self) -> usize {
+        self.cores.iter().filter_map(|core| core.package).collect::<HashSet<_>>().len().max(1)
+    }
+
+    pub fn physical_cores(&self) -> usize {
+        let cores = self.cores.iter().filter_map(|core| Some((core.package?, core.core?))).collect::<HashSet<_>>();
+        match cores.is_empty() {
+            true => self.cores.len(),
+            false => cores.len(),
+        }
+    }
+
+    pub fn numa_nodes(&self) -> usize {
+        self.cores.iter().filter_map(|core| core.numa_node).collect::<HashSet<_>>().len().max(1)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,129 @@
+use std::fs;
+
+pub const CPU_HISTORY: usize = 60;
+
+// Cumulative jiffies from one "cpu"/"cpuN" line of /proc/stat.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    pub guest: u64,
+    pub guest_nice: u64,
+}
+
+// Percentages of the interval between two samples; they add up to 100.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    pub steal: f32,
+    pub guest: f32,
+}
+
+pub const BREAKDOWN_FIELDS: [(&str, &str); 9] = [
+    ("user", "hsl(200, 70%, 55%)"),
+    ("nice", "hsl(170, 60%, 50%)"),
+    ("system", "hsl(15, 80%, 60%)"),
+    ("iowait", "hsl(45, 90%, 55%)"),
+    ("irq", "hsl(280, 50%, 60%)"),
+    ("softirq", "hsl(310, 50%, 65%)"),
+    ("steal", "hsl(0, 80%, 50%)"),
+    ("guest", "hsl(120, 40%, 50%)"),
+    ("idle", "hsl(0, 0%, 25%)"),
+];
+
+impl CpuTimes {
+    // The kernel already counts guest time inside user (and guest_nice inside nice).
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+}
+
+impl CpuBreakdown {
+    pub fn between(previous: &CpuTimes, current: &CpuTimes) -> CpuBreakdown {
+        let total = current.total().saturating_sub(previous.total());
+        if total == 0 {
+            return CpuBreakdown::default();
+        }
+        let percent = |now: u64, before: u64| now.saturating_sub(before) as f32 * 100.0 / total as f32;
+        let guest = percent(current.guest + current.guest_nice, previous.guest + previous.guest_nice);
+        CpuBreakdown {
+            user: (percent(current.user, previous.user) - percent(current.guest, previous.guest)).max(0.0),
+            nice: (percent(current.nice, previous.nice) - percent(current.guest_nice, previous.guest_nice)).max(0.0),
+            system: percent(current.system, previous.system),
+            idle: percent(current.idle, previous.idle),
+            iowait: percent(current.iowait, previous.iowait),
+            irq: percent(current.irq, previous.irq),
+            softirq: percent(current.softirq, previous.softirq),
+            steal: percent(current.steal, previous.steal),
+            guest,
+        }
+    }
+
+    pub fn get(&self, field: &str) -> f32 {
+        match field {
+            "user" => self.user,
+            "nice" => self.nice,
+            "system" => self.system,
+            "idle" => self.idle,
+            "iowait" => self.iowait,
+            "irq" => self.irq,
+            "softirq" => self.softirq,
+            "steal" => self.steal,
+            "guest" => self.guest,
+            _ => 0.0,
+        }
+    }
+}
+
+pub fn parse_cpu_times(content: &str) -> Vec<(String, CpuTimes)> {
+    content
+        .lines()
+        .filter(|line| line.starts_with("cpu"))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?.to_string();
+            let values = fields.map(|value| value.parse::<u64>().unwrap_or(0)).collect::<Vec<_>>();
+            // Older kernels stop after irq/softirq; missing columns count as zero.
+            let value = |index: usize| values.get(index).copied().unwrap_or(0);
+            Some((name, CpuTimes {
+                user: value(0),
+                nice: value(1),
+                system: value(2),
+                idle: value(3),
+                iowait: value(4),
+                irq: value(5),
+                softirq: value(6),
+                steal: value(7),
+                guest: value(8),
+                guest_nice: value(9),
+            }))
+        })
+        .collect()
+}
+
+pub fn read_cpu_times() -> Vec<(String, CpuTimes)> {
+    fs::read_to_string("/proc/stat").map(|content| parse_cpu_times(&content)).unwrap_or_default()
+}
+
+// Pairs each CPU with its previous sample; CPUs that just came online have none yet.
+pub fn breakdowns(previous: &[(String, CpuTimes)], current: &[(String, CpuTimes)]) -> Vec<(String, CpuBreakdown)> {
+    current
+        .iter()
+        .filter_map(|(name, times)| {
+            let (_, before) = previous.iter().find(|(previous_name, _)| previous_name.eq(name))?;
+            Some((name.clone(), CpuBreakdown::between(before, times)))
+        })
+        .collect()
+}
\ No newline at end of file
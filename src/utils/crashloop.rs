@@ -0,0 +1,137 @@
+use std::collections::{ HashMap, HashSet };
+
+use super::events::{ EventKind, ProcessEvent };
+
+pub const DEFAULT_RESTARTS: usize = 3;
+pub const DEFAULT_WINDOW: u64 = 60;
+
+#[derive(Clone, PartialEq)]
+pub struct CrashLoop {
+    pub cmd: String,
+    pub restarts: usize,
+    pub last_exit: u64,
+    pub average_lifetime: Option<f64>,
+}
+
+// Polling can't see exit codes, so an exit with unknown status is treated as a possible crash.
+fn is_abnormal(event: &ProcessEvent) -> bool {
+    event.exit_status != Some(0)
+}
+
+// A restart is an abnormal exit followed later by a new process running the same command under the
+// same parent (the supervisor, or systemd for a unit). Exits are grouped by command line, since every
+// restart gets a new PID. Events are expected in the order they were recorded.
+pub fn detect_crash_loops(events: &[ProcessEvent], max_restarts: usize, window: u64, now: u64) -> Vec<CrashLoop> {
+    let since = now.saturating_sub(window);
+    let recent = events.iter().filter(|event| event.timestamp >= since).collect::<Vec<_>>();
+
+    // The proc connector reports a start at fork time, before exec, so the command a started
+    // process ends up running is taken from its own exit when there is one.
+    let mut exit_cmd: HashMap<u32, &str> = HashMap::new();
+    let mut restarted = vec![false; recent.len()];
+    let mut later_starts: HashSet<(Option<u32>, &str)> = HashSet::new();
+    for (index, event) in recent.iter().enumerate().rev() {
+        match event.kind {
+            EventKind::Exited => {
+                restarted[index] = later_starts.contains(&(event.parent, event.cmd.as_str()));
+                exit_cmd.insert(event.pid, &event.cmd);
+            }
+            EventKind::Started => {
+                let cmd = exit_cmd.remove(&event.pid).unwrap_or(&event.cmd);
+                later_starts.insert((event.parent, cmd));
+            }
+        }
+    }
+
+    let mut loops: Vec<(CrashLoop, Vec<u64>)> = Vec::new();
+    for (event, _) in recent.iter().zip(restarted).filter(|(event, restarted)| *restarted && is_abnormal(event) && !event.cmd.is_empty()) {
+        let index = match loops.iter().position(|(crash_loop, _)| crash_loop.cmd.eq(&event.cmd)) {
+            Some(index) => index,
+            None => {
+                loops.push((CrashLoop { cmd: event.cmd.clone(), restarts: 0, last_exit: 0, average_lifetime: None }, Vec::new()));
+                loops.len() - 1
+            }
+        };
+        let (crash_loop, lifetimes) = &mut loops[index];
+        crash_loop.restarts += 1;
+        crash_loop.last_exit = crash_loop.last_exit.max(event.timestamp);
+        if let Some(lifetime) = event.lifetime {
+            lifetimes.push(lifetime);
+        }
+    }
+
+    let mut flagged = loops
+        .into_iter()
+        .filter(|(crash_loop, _)| crash_loop.restarts > max_restarts)
+        .map(|(mut crash_loop, lifetimes)| {
+            if !lifetimes.is_empty() {
+                crash_loop.average_lifetime = Some(lifetimes.iter().sum::<u64>() as f64 / lifetimes.len() as f64);
+            }
+            crash_loop
+        })
+        .collect::<Vec<_>>();
+    flagged.sort_by_key(|crash_loop| std::cmp::Reverse(crash_loop.restarts));
+    flagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: EventKind, timestamp: u64, pid: u32, cmd: &str, exit_status: Option<u32>) -> ProcessEvent {
+        ProcessEvent { kind, timestamp, pid, name: "worker".to_string(), cmd: cmd.to_string(), parent: Some(1), lifetime: Some(2), exit_status }
+    }
+
+    fn crashing(restarts: u32, exit_status: Option<u32>) -> Vec<ProcessEvent> {
+        let mut events = vec![event(EventKind::Started, 100, 10, "worker --serve", None)];
+        for restart in 0..restarts {
+            let pid = 10 + restart;
+            events.push(event(EventKind::Exited, 102 + restart as u64 * 3, pid, "worker --serve", exit_status));
+            events.push(event(EventKind::Started, 103 + restart as u64 * 3, pid + 1, "worker --serve", None));
+        }
+        events
+    }
+
+    #[test]
+    fn counts_abnormal_exits_followed_by_a_restart() {
+        let loops = detect_crash_loops(&crashing(5, Some(256)), 3, 60, 120);
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].restarts, 5);
+        assert_eq!(loops[0].average_lifetime, Some(2.0));
+    }
+
+    #[test]
+    fn ignores_clean_exits() {
+        assert!(detect_crash_loops(&crashing(5, Some(0)), 3, 60, 120).is_empty());
+    }
+
+    #[test]
+    fn ignores_exits_that_are_not_restarted() {
+        let events = (0..5).map(|pid| event(EventKind::Exited, 100 + pid as u64, pid, "make -j8", Some(256))).collect::<Vec<_>>();
+        assert!(detect_crash_loops(&events, 3, 60, 120).is_empty());
+    }
+
+    #[test]
+    fn requires_the_same_parent() {
+        let mut events = crashing(5, Some(256));
+        for event in events.iter_mut().filter(|event| event.kind == EventKind::Started) {
+            event.parent = Some(event.pid + 100);
+        }
+        assert!(detect_crash_loops(&events, 3, 60, 120).is_empty());
+    }
+
+    #[test]
+    fn takes_the_command_of_a_forked_start_from_its_exit() {
+        let mut events = crashing(5, Some(256));
+        for event in events.iter_mut().filter(|event| event.kind == EventKind::Started) {
+            event.cmd = "supervisord".to_string();
+        }
+        // The last restart is still running and only has its pre-exec command.
+        assert_eq!(detect_crash_loops(&events, 3, 60, 120)[0].restarts, 4);
+    }
+
+    #[test]
+    fn only_looks_inside_the_window() {
+        assert!(detect_crash_loops(&crashing(5, None), 3, 5, 120).is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::app_props::MyProcess;
+use super::functions::format_timestamp;
+
+pub const MAX_EVENTS: usize = 5000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Started,
+    Exited,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ProcessEvent {
+    pub kind: EventKind,
+    pub timestamp: u64,
+    pub pid: u32,
+    pub name: String,
+    pub cmd: String,
+    pub parent: Option<u32>,
+    pub lifetime: Option<u64>,
+    // Raw wait status of an exit, when the source reports it (the proc connector does, polling doesn't).
+    pub exit_status: Option<u32>,
+}
+
+impl EventKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventKind::Started => "Iniciado",
+            EventKind::Exited => "Encerrado",
+        }
+    }
+}
+
+impl ProcessEvent {
+    pub fn matches(&self, filter: &str) -> bool {
+        self.name.contains(filter) || self.cmd.contains(filter) || self.pid.to_string().contains(filter)
+    }
+}
+
+// A PID is only "the same process" if its start time also matches, otherwise it was reused.
+fn process_keys(procs: &[MyProcess]) -> HashSet<(u32, u64)> {
+    procs.iter().map(|proc| (proc.pid, proc.start_time)).collect()
+}
+
+pub fn diff_snapshots(previous: &[MyProcess], current: &[MyProcess], now: u64) -> Vec<ProcessEvent> {
+    let (previous_keys, current_keys) = (process_keys(previous), process_keys(current));
+    let mut events = Vec::new();
+    for proc in previous {
+        if !current_keys.contains(&(proc.pid, proc.start_time)) {
+            events.push(ProcessEvent {
+                kind: EventKind::Exited,
+                timestamp: now,
+                pid: proc.pid,
+                name: proc.name.clone(),
+                cmd: proc.cmd.clone(),
+                parent: proc.parent,
+                lifetime: Some(now.saturating_sub(proc.start_time)),
+                exit_status: None,
+            });
+        }
+    }
+    for proc in current {
+        if !previous_keys.contains(&(proc.pid, proc.start_time)) {
+            events.push(ProcessEvent {
+                kind: EventKind::Started,
+                timestamp: proc.start_time,
+                pid: proc.pid,
+                name: proc.name.clone(),
+                cmd: proc.cmd.clone(),
+                parent: proc.parent,
+                lifetime: None,
+                exit_status: None,
+            });
+        }
+    }
+    events
+}
+
+pub fn export_csv(events: &[ProcessEvent], path: &Path) -> io::Result<()> {
+    let mut csv = String::from("timestamp,evento,pid,nome,comando,pai,duracao_s\n");
+    for event in events {
+        csv.push_str(&format!(
+            "{},{},{},\"{}\",\"{}\",{},{}\n",
+            format_timestamp(event.timestamp),
+            event.kind.label(),
+            event.pid,
+            event.name.replace('"', "\"\""),
+            event.cmd.replace('"', "\"\""),
+            event.parent.map(|pid| pid.to_string()).unwrap_or_default(),
+            event.lifetime.map(|secs| secs.to_string()).unwrap_or_default(),
+        ));
+    }
+    fs::write(path, csv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::namespaces::Namespaces;
+
+    fn proc(pid: u32, start_time: u64) -> MyProcess {
+        MyProcess {
+            pid,
+            name: format!("proc-{}", pid),
+            memory: 0,
+            cpu_usage: 0.0,
+            read_bytes: 0,
+            written_bytes: 0,
+            namespaces: Namespaces::default(),
+            parent: Some(1),
+            cgroup: String::new(),
+            frozen: false,
+            oom_score: 0,
+            oom_score_adj: 0,
+            start_time,
+            cmd: format!("proc-{} --run", pid),
+            state: 'S',
+            wchan: String::new(),
+        }
+    }
+
+    fn kinds(events: &[ProcessEvent]) -> Vec<(EventKind, u32, u64)> {
+        events.iter().map(|event| (event.kind, event.pid, event.timestamp)).collect()
+    }
+
+    #[test]
+    fn reports_started_and_exited_processes() {
+        let events = diff_snapshots(&[proc(1, 10), proc(2, 20)], &[proc(1, 10), proc(3, 95)], 100);
+        assert!(kinds(&events) == vec![(EventKind::Exited, 2, 100), (EventKind::Started, 3, 95)]);
+        assert_eq!(events[0].lifetime, Some(80));
+        assert_eq!(events[1].lifetime, None);
+    }
+
+    #[test]
+    fn a_reused_pid_is_an_exit_and_a_start() {
+        let events = diff_snapshots(&[proc(7, 10)], &[proc(7, 90)], 100);
+        assert!(kinds(&events) == vec![(EventKind::Exited, 7, 100), (EventKind::Started, 7, 90)]);
+    }
+
+    #[test]
+    fn unchanged_snapshots_have_no_events() {
+        let procs = (1..500).map(|pid| proc(pid, pid as u64)).collect::<Vec<_>>();
+        assert!(diff_snapshots(&procs, &procs, 100).is_empty());
+    }
+}
\ No newline at end of file
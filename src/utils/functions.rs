@@ -1,3 +1,7 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
 use image::GenericImageView;
 use dioxus_desktop::tao::window:: Icon as TaoIcon;
 
@@ -9,4 +13,42 @@ pub(crate) fn load_icon_by_path(file_path: &str) -> Option<TaoIcon> {
     } else {
         None
     }
+}
+
+// Single-value files under /sys and /proc; a missing or empty file reads as None.
+pub(crate) fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|content| content.trim().to_string()).filter(|content| !content.is_empty())
+}
+
+pub(crate) fn read_number<T: FromStr>(path: &Path) -> Option<T> {
+    read_trimmed(path)?.parse().ok()
+}
+
+pub(crate) fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+// UTC, "YYYY-MM-DD HH:MM:SS"; days are converted with the civil-from-days algorithm.
+pub(crate) fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let time = secs % 86400;
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02}", year, month, day, time / 3600, time % 3600 / 60, time % 60)
+}
+
+// Green at idle, through yellow, to red at one full core or more.
+pub(crate) fn cpu_colour(cpu: f32) -> String {
+    let hue = 120.0 - (cpu.clamp(0.0, 100.0) / 100.0 * 120.0);
+    format!("hsl({:.0}, 70%, 50%)", hue)
 }
\ No newline at end of file
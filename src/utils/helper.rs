@@ -0,0 +1,124 @@
+use std::io::{ self, BufRead, BufReader, Write };
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::{ Command, Stdio };
+use std::sync::Mutex;
+use std::time::{ Duration, Instant };
+
+use super::helper_protocol::{ peer_uid, Request };
+use super::protection::{ authorize, Origin };
+use super::settings::settings_path;
+
+const HELPER_NAME: &str = "task-manager-helper";
+// pkexec shows a password prompt, so give the user time to answer it.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+static STARTING: Mutex<()> = Mutex::new(());
+
+pub fn socket_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join(format!("{}.sock", HELPER_NAME)),
+        _ => std::env::temp_dir().join(format!("{}-{}.sock", HELPER_NAME, unsafe { libc::getuid() })),
+    }
+}
+
+// Next to our own executable, which is where cargo and packages put it.
+fn helper_path() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(HELPER_NAME)))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| PathBuf::from(HELPER_NAME))
+}
+
+// Blocks until the password prompt is answered, so it's only called from a thread started by an explicit click.
+pub fn start_helper() -> Result<(), String> {
+    // Two clicks in a row mustn't open two password prompts.
+    let _starting = STARTING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if helper_running() {
+        return Ok(());
+    }
+    let helper = helper_path();
+    let socket = socket_path();
+    let uid = unsafe { libc::getuid() }.to_string();
+    let args = [
+        helper.to_string_lossy().to_string(),
+        "--socket".to_string(),
+        socket.to_string_lossy().to_string(),
+        "--allow-uid".to_string(),
+        uid,
+        "--settings".to_string(),
+        settings_path().to_string_lossy().to_string(),
+    ];
+    let elevate = std::env::var("TASK_MANAGER_ELEVATE").unwrap_or_else(|_| "pkexec".to_string());
+    let mut command = match elevate.as_str() {
+        "sudo" => Command::new("sudo"),
+        _ => Command::new("pkexec"),
+    };
+    if elevate.eq("sudo") {
+        // No terminal to ask for a password in; only works with cached credentials or NOPASSWD.
+        command.arg("-n");
+    }
+    let mut child = command
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("Erro ao iniciar o auxiliar com {}: {}", elevate, err))?;
+
+    // pkexec and sudo stay around as long as the helper runs, so an early exit means it never started.
+    let deadline = Instant::now() + STARTUP_TIMEOUT;
+    while Instant::now() < deadline {
+        if connect().is_ok() {
+            return Ok(());
+        }
+        match child.try_wait() {
+            Ok(Some(status)) => return Err(format!("{} terminou sem iniciar o auxiliar ({}): autenticação cancelada ou negada?", elevate, status)),
+            Ok(None) => {}
+            Err(err) => return Err(format!("Erro ao aguardar {}: {}", elevate, err)),
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+    Err("O auxiliar privilegiado não respondeu a tempo".to_string())
+}
+
+pub fn helper_running() -> bool {
+    connect().is_ok()
+}
+
+// Never starts the helper: that takes a password prompt, which only the user may ask for.
+// Without XDG_RUNTIME_DIR the socket lives in /tmp, where anyone could bind it first,
+// so only a socket served by root counts as the helper.
+fn connect() -> Result<UnixStream, String> {
+    let socket = socket_path();
+    let stream = UnixStream::connect(&socket).map_err(|_| "sem permissão; use \"Obter permissão de administrador\" e tente de novo".to_string())?;
+    match peer_uid(&stream) {
+        Some(0) => Ok(stream),
+        uid => Err(format!("{} não é servido pelo root (uid {:?}); recusando", socket.display(), uid)),
+    }
+}
+
+pub fn send_to_helper(request: &Request) -> Result<(), String> {
+    let mut stream = connect()?;
+    writeln!(stream, "{}", request.to_line()).map_err(|err| format!("Erro ao falar com o auxiliar: {}", err))?;
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response).map_err(|err| format!("Erro ao falar com o auxiliar: {}", err))?;
+    match response.trim() {
+        "ok" => Ok(()),
+        response => Err(format!("auxiliar: {}", response.strip_prefix("err ").unwrap_or(response))),
+    }
+}
+
+// Tries the action ourselves first and only goes through an already running helper when the kernel says no.
+// Automatic callers never use it: the user granted root for their own clicks, not for unattended rules.
+pub fn perform(request: Request, origin: Origin) -> Result<(), String> {
+    let request = request.validate()?;
+    authorize(request.pid(), origin)?;
+    match (request.execute(), origin) {
+        (Ok(()), _) => Ok(()),
+        (Err(err), Origin::User { .. }) if err.kind() == io::ErrorKind::PermissionDenied => send_to_helper(&request),
+        (Err(err), _) => Err(err.to_string()),
+    }
+}
\ No newline at end of file
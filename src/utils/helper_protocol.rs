@@ -0,0 +1,269 @@
+// Shared by the GUI and src/bin/task-manager-helper.rs, so it only depends on std and libc.
+use std::fs;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::path::{ Component, Path };
+
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+pub const MANAGED_GROUP: &str = "task-manager";
+// Highest value /proc/sys/kernel/pid_max accepts.
+const PID_MAX: u32 = 4194304;
+const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+// One request per line, answered with "ok" or "err <message>".
+#[derive(Clone, PartialEq, Debug)]
+pub enum Request {
+    Signal { pid: u32, signal: i32 },
+    Renice { pid: u32, nice: i32 },
+    Ionice { pid: u32, class: i32, level: i32 },
+    Affinity { pid: u32, cpus: Vec<usize> },
+    OomScoreAdj { pid: u32, value: i32 },
+    Cgroup { pid: u32, cgroup: String },
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<&str>, what: &str) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("faltou {}", what))?;
+    value.parse().map_err(|_| format!("{} inválido: {}", what, value))
+}
+
+fn parse_pid(value: Option<&str>) -> Result<u32, String> {
+    let pid = parse_number::<u32>(value, "pid")?;
+    // 0 and anything that turns negative as pid_t would address process groups or everyone.
+    if pid == 0 || pid > PID_MAX {
+        return Err(format!("pid fora do intervalo: {}", pid));
+    }
+    Ok(pid)
+}
+
+fn check_range(value: i32, min: i32, max: i32, what: &str) -> Result<i32, String> {
+    match (min..=max).contains(&value) {
+        true => Ok(value),
+        false => Err(format!("{} deve estar entre {} e {}", what, min, max)),
+    }
+}
+
+// "0,2-3" style, as in taskset and /proc/<pid>/status.
+pub fn parse_cpu_list(list: &str) -> Result<Vec<usize>, String> {
+    let mut cpus = Vec::new();
+    for part in list.split(',').map(|part| part.trim()).filter(|part| !part.is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (first, last),
+            None => (part, part),
+        };
+        let first = first.trim().parse::<usize>().map_err(|_| format!("CPU inválida: {}", part))?;
+        let last = last.trim().parse::<usize>().map_err(|_| format!("CPU inválida: {}", part))?;
+        if first > last || last >= libc::CPU_SETSIZE as usize {
+            return Err(format!("intervalo de CPUs inválido: {}", part));
+        }
+        cpus.extend(first..=last);
+    }
+    if cpus.is_empty() {
+        return Err("informe ao menos uma CPU".to_string());
+    }
+    Ok(cpus)
+}
+
+// Only plain relative paths below the cgroup root, so "../" can't reach other files.
+fn check_cgroup(cgroup: &str) -> Result<String, String> {
+    let relative = cgroup.trim().trim_start_matches('/');
+    if relative.is_empty() || !Path::new(relative).components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(format!("cgroup inválido: {}", cgroup));
+    }
+    Ok(relative.to_string())
+}
+
+// Only the unified (v2) hierarchy is considered, which is the "0::" line.
+pub fn process_cgroup(pid: u32) -> Option<String> {
+    let content = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| path.to_string())
+}
+
+// Fields of /proc/<pid>/stat after the command name, which may itself contain spaces.
+pub fn stat_field(pid: u32, index: usize) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    stat.rsplit_once(')')?.1.split_whitespace().nth(index)?.parse().ok()
+}
+
+// In clock ticks since boot; together with the pid it names one process even across pid reuse.
+pub fn read_start_time(pid: u32) -> Option<u64> {
+    stat_field(pid, 19)
+}
+
+// The uid of the process on the other end, as the kernel saw it when connecting.
+pub fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    let mut credentials: libc::ucred = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut credentials as *mut libc::ucred as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    match result {
+        0 => Some(credentials.uid),
+        _ => None,
+    }
+}
+
+impl Request {
+    pub fn parse(line: &str) -> Result<Request, String> {
+        let mut words = line.split_whitespace();
+        let verb = words.next().ok_or("requisição vazia")?;
+        let pid = parse_pid(words.next())?;
+        let request = match verb {
+            "signal" => Request::Signal { pid, signal: check_range(parse_number(words.next(), "sinal")?, 1, 64, "sinal")? },
+            "renice" => Request::Renice { pid, nice: check_range(parse_number(words.next(), "nice")?, -20, 19, "nice")? },
+            "ionice" => Request::Ionice {
+                pid,
+                class: check_range(parse_number(words.next(), "classe")?, 1, 3, "classe de E/S")?,
+                level: check_range(parse_number(words.next(), "nível")?, 0, 7, "nível de E/S")?,
+            },
+            "affinity" => Request::Affinity { pid, cpus: parse_cpu_list(words.next().ok_or("faltou a lista de CPUs")?)? },
+            "oom_score_adj" => Request::OomScoreAdj { pid, value: check_range(parse_number(words.next(), "oom_score_adj")?, -1000, 1000, "oom_score_adj")? },
+            "cgroup" => Request::Cgroup { pid, cgroup: check_cgroup(words.next().ok_or("faltou o cgroup")?)? },
+            _ => return Err(format!("ação desconhecida: {}", verb)),
+        };
+        match words.next() {
+            Some(extra) => Err(format!("argumento inesperado: {}", extra)),
+            None => Ok(request),
+        }
+    }
+
+    pub fn pid(&self) -> u32 {
+        match self {
+            Request::Signal { pid, .. }
+            | Request::Renice { pid, .. }
+            | Request::Ionice { pid, .. }
+            | Request::Affinity { pid, .. }
+            | Request::OomScoreAdj { pid, .. }
+            | Request::Cgroup { pid, .. } => *pid,
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        match self {
+            Request::Signal { pid, signal } => format!("signal {} {}", pid, signal),
+            Request::Renice { pid, nice } => format!("renice {} {}", pid, nice),
+            Request::Ionice { pid, class, level } => format!("ionice {} {} {}", pid, class, level),
+            Request::Affinity { pid, cpus } => format!("affinity {} {}", pid, cpus.iter().map(|cpu| cpu.to_string()).collect::<Vec<_>>().join(",")),
+            Request::OomScoreAdj { pid, value } => format!("oom_score_adj {} {}", pid, value),
+            Request::Cgroup { pid, cgroup } => format!("cgroup {} {}", pid, cgroup),
+        }
+    }
+
+    // Round-trips through the textual form, so the GUI validates exactly what the helper would.
+    pub fn validate(&self) -> Result<Request, String> {
+        Request::parse(&self.to_line())
+    }
+
+    pub fn execute(&self) -> io::Result<()> {
+        let result = match self {
+            Request::Signal { pid, signal } => unsafe { libc::kill(*pid as libc::pid_t, *signal) },
+            Request::Renice { pid, nice } => unsafe { libc::setpriority(libc::PRIO_PROCESS, *pid as libc::id_t, *nice) },
+            Request::Ionice { pid, class, level } => unsafe {
+                libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, *pid as libc::c_int, (class << IOPRIO_CLASS_SHIFT) | level) as libc::c_int
+            },
+            Request::Affinity { pid, cpus } => unsafe {
+                let mut set: libc::cpu_set_t = std::mem::zeroed();
+                for cpu in cpus {
+                    libc::CPU_SET(*cpu, &mut set);
+                }
+                libc::sched_setaffinity(*pid as libc::pid_t, std::mem::size_of::<libc::cpu_set_t>(), &set)
+            },
+            Request::OomScoreAdj { pid, value } => return fs::write(format!("/proc/{}/oom_score_adj", pid), value.to_string()),
+            Request::Cgroup { pid, cgroup } => return fs::write(Path::new(CGROUP_ROOT).join(cgroup).join("cgroup.procs"), pid.to_string()),
+        };
+        match result {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_request() {
+        assert_eq!(Request::parse("signal 42 15"), Ok(Request::Signal { pid: 42, signal: 15 }));
+        assert_eq!(Request::parse("renice 42 -5"), Ok(Request::Renice { pid: 42, nice: -5 }));
+        assert_eq!(Request::parse("ionice 42 2 7"), Ok(Request::Ionice { pid: 42, class: 2, level: 7 }));
+        assert_eq!(Request::parse("affinity 42 0,2-3"), Ok(Request::Affinity { pid: 42, cpus: vec![0, 2, 3] }));
+        assert_eq!(Request::parse("oom_score_adj 42 -1000"), Ok(Request::OomScoreAdj { pid: 42, value: -1000 }));
+        assert_eq!(Request::parse("cgroup 42 /task-manager/pid-42"), Ok(Request::Cgroup { pid: 42, cgroup: "task-manager/pid-42".to_string() }));
+    }
+
+    #[test]
+    fn round_trips_through_the_line_format() {
+        let requests = [
+            Request::Signal { pid: 1234, signal: 9 },
+            Request::Ionice { pid: 1234, class: 3, level: 0 },
+            Request::Affinity { pid: 1234, cpus: vec![1, 5, 6] },
+            Request::Cgroup { pid: 1234, cgroup: "task-manager/launch-1".to_string() },
+        ];
+        for request in requests {
+            assert_eq!(request.validate(), Ok(request.clone()));
+            assert_eq!(request.pid(), 1234);
+        }
+    }
+
+    #[test]
+    fn rejects_pids_that_address_groups() {
+        assert!(Request::parse("signal 0 15").is_err());
+        assert!(Request::parse("signal -1 15").is_err());
+        assert!(Request::parse("signal 4294967295 15").is_err());
+        assert!(Request::parse(&format!("signal {} 15", PID_MAX + 1)).is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_values() {
+        for line in ["signal 42 0", "signal 42 65", "renice 42 20", "renice 42 -21", "ionice 42 4 0", "ionice 42 2 8", "oom_score_adj 42 1001"] {
+            assert!(Request::parse(line).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for line in ["", "signal", "signal 42", "signal 42 15 extra", "reboot 42", "renice abc 1", "ionice 42 2"] {
+            assert!(Request::parse(line).is_err(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn keeps_cgroup_paths_below_the_root() {
+        for cgroup in ["../../etc", "task-manager/../../etc", "/", "./x"] {
+            assert!(Request::parse(&format!("cgroup 42 {}", cgroup)).is_err(), "{}", cgroup);
+        }
+    }
+
+    #[test]
+    fn parses_cpu_lists() {
+        assert_eq!(parse_cpu_list("0"), Ok(vec![0]));
+        assert_eq!(parse_cpu_list(" 0, 2-4 ,7"), Ok(vec![0, 2, 3, 4, 7]));
+        assert_eq!(parse_cpu_list("3-3"), Ok(vec![3]));
+    }
+
+    #[test]
+    fn rejects_bad_cpu_lists() {
+        for list in ["", ",", "4-2", "a", "1-", "-1", &libc::CPU_SETSIZE.to_string()] {
+            assert!(parse_cpu_list(list).is_err(), "{:?}", list);
+        }
+    }
+
+    #[test]
+    fn reads_fields_after_the_command_name() {
+        let pid = std::process::id();
+        assert_eq!(stat_field(pid, 1), Some(unsafe { libc::getppid() } as u64));
+        assert!(read_start_time(pid).is_some());
+        assert_eq!(read_start_time(u32::MAX), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::collections::{ HashMap, HashSet };
+
+use super::app_props::MyProcess;
+
+pub const MAX_HISTORY: usize = 2000;
+
+#[derive(Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub pid: u32,
+    pub name: String,
+    pub cmd: String,
+    pub start_time: u64,
+    pub last_seen: u64,
+    pub exited: bool,
+    pub cpu_total: f64,
+    pub samples: u32,
+    pub peak_cpu: f32,
+    pub peak_memory: u64,
+}
+
+impl HistoryEntry {
+    pub fn average_cpu(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        (self.cpu_total / self.samples as f64) as f32
+    }
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct ProcessHistory {
+    pub started_at: u64,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl ProcessHistory {
+    pub fn update(&mut self, procs: &[MyProcess], now: u64) {
+        if self.started_at == 0 {
+            self.started_at = now;
+        }
+        // The pid alone can be reused, so entries are keyed by (pid, start time).
+        let mut index = self.entries.iter().enumerate().map(|(at, entry)| ((entry.pid, entry.start_time), at)).collect::<HashMap<_, _>>();
+        let live = procs.iter().map(|proc| (proc.pid, proc.start_time)).collect::<HashSet<_>>();
+        for entry in self.entries.iter_mut().filter(|entry| !entry.exited) {
+            if !live.contains(&(entry.pid, entry.start_time)) {
+                entry.exited = true;
+            }
+        }
+        for proc in procs {
+            let at = *index.entry((proc.pid, proc.start_time)).or_insert_with(|| {
+                self.entries.push(HistoryEntry {
+                    pid: proc.pid,
+                    name: proc.name.clone(),
+                    cmd: proc.cmd.clone(),
+                    start_time: proc.start_time,
+                    last_seen: now,
+                    exited: false,
+                    cpu_total: 0.0,
+                    samples: 0,
+                    peak_cpu: 0.0,
+                    peak_memory: 0,
+                });
+                self.entries.len() - 1
+            });
+            let entry = &mut self.entries[at];
+            entry.last_seen = now;
+            entry.cpu_total += proc.cpu_usage as f64;
+            entry.samples += 1;
+            entry.peak_cpu = entry.peak_cpu.max(proc.cpu_usage);
+            entry.peak_memory = entry.peak_memory.max(proc.memory);
+        }
+        self.evict();
+    }
+
+    // Once the history is full the finished processes that ended longest ago go first.
+    // Running processes are never dropped, so the history only outgrows the limit while they do.
+    fn evict(&mut self) {
+        let Some(excess) = self.entries.len().checked_sub(MAX_HISTORY).filter(|excess| *excess > 0) else {
+            return;
+        };
+        let mut exited = self.entries.iter().filter(|entry| entry.exited).map(|entry| (entry.last_seen, entry.pid, entry.start_time)).collect::<Vec<_>>();
+        exited.sort_unstable();
+        let dropped = exited.into_iter().take(excess).map(|(_, pid, start_time)| (pid, start_time)).collect::<HashSet<_>>();
+        self.entries.retain(|entry| !dropped.contains(&(entry.pid, entry.start_time)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::namespaces::Namespaces;
+
+    fn proc(pid: u32, start_time: u64, cpu_usage: f32) -> MyProcess {
+        MyProcess {
+            pid,
+            name: format!("proc-{}", pid),
+            memory: 10,
+            cpu_usage,
+            read_bytes: 0,
+            written_bytes: 0,
+            namespaces: Namespaces::default(),
+            parent: Some(1),
+            cgroup: String::new(),
+            frozen: false,
+            oom_score: 0,
+            oom_score_adj: 0,
+            start_time,
+            cmd: format!("proc-{} --run", pid),
+            state: 'S',
+            wchan: String::new(),
+        }
+    }
+
+    #[test]
+    fn accumulates_samples_per_process() {
+        let mut history = ProcessHistory::default();
+        history.update(&[proc(10, 100, 20.0), proc(11, 100, 0.0)], 200);
+        history.update(&[proc(10, 100, 40.0)], 201);
+        assert_eq!(history.started_at, 200);
+        assert_eq!(history.entries.len(), 2);
+        let first = &history.entries[0];
+        assert!(!first.exited && first.samples == 2 && first.peak_cpu == 40.0 && first.last_seen == 201);
+        assert_eq!(first.average_cpu(), 30.0);
+        assert!(history.entries[1].exited);
+    }
+
+    #[test]
+    fn reused_pid_gets_a_new_entry() {
+        let mut history = ProcessHistory::default();
+        history.update(&[proc(10, 100, 5.0)], 200);
+        history.update(&[proc(10, 150, 5.0)], 201);
+        assert_eq!(history.entries.len(), 2);
+        assert!(history.entries[0].exited && !history.entries[1].exited);
+    }
+
+    #[test]
+    fn evicts_the_oldest_finished_processes_only() {
+        let mut history = ProcessHistory::default();
+        let live = (0..MAX_HISTORY as u32 - 1).map(|pid| proc(pid, 1, 0.0)).collect::<Vec<_>>();
+        history.update(&[proc(90000, 1, 0.0), proc(90001, 1, 0.0)], 10);
+        history.update(&[proc(90001, 1, 0.0)], 11);
+        history.update(&live, 12);
+        assert_eq!(history.entries.len(), MAX_HISTORY);
+        assert!(history.entries.iter().all(|entry| entry.pid != 90000));
+        assert!(history.entries.iter().any(|entry| entry.pid == 90001 && entry.exited));
+        // Running processes stay even past the limit.
+        let more = live.iter().cloned().chain([proc(90002, 1, 0.0), proc(90003, 1, 0.0)]).collect::<Vec<_>>();
+        history.update(&more, 13);
+        assert_eq!(history.entries.len(), MAX_HISTORY + 1);
+        assert!(history.entries.iter().all(|entry| !entry.exited));
+    }
+}
\ No newline at end of file
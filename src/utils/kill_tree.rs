@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::time::{ Duration, Instant };
+
+use super::app_props::MyProcess;
+use super::helper_protocol::read_start_time;
+use super::protection::Origin;
+use super::signals::{ send_signal, signal_name };
+use super::state::read_state;
+use super::tree::descendants;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Escalation {
+    pub first_signal: i32,
+    pub grace: Duration,
+    pub final_signal: Option<i32>,
+}
+
+impl Escalation {
+    pub fn default() -> Escalation {
+        Escalation { first_signal: libc::SIGTERM, grace: Duration::from_secs(5), final_signal: Some(libc::SIGKILL) }
+    }
+}
+
+// Children first, so nothing gets re-parented to init while we're still walking the tree.
+pub fn kill_order(root: u32, procs: &[MyProcess]) -> Vec<u32> {
+    let mut order = descendants(root, procs);
+    order.reverse();
+    order.push(root);
+    order
+}
+
+pub fn is_alive(pid: u32) -> bool {
+    Path::new(&format!("/proc/{}", pid)).exists() && read_state(pid) != 'Z'
+}
+
+// A pid that exited during the grace period may already belong to a new process,
+// so it only counts as the same one while its start time is unchanged.
+pub fn is_same_alive(pid: u32, start_time: u64) -> bool {
+    is_alive(pid) && read_start_time(pid) == Some(start_time)
+}
+
+pub fn kill_tree(pids: &[u32], escalation: Escalation, origin: Origin) -> String {
+    let targets = pids.iter().filter_map(|pid| Some((*pid, read_start_time(*pid)?))).collect::<Vec<_>>();
+    let mut errors = Vec::new();
+    for (pid, start_time) in targets.iter() {
+        if !is_same_alive(*pid, *start_time) {
+            continue;
+        }
+        if let Err(err) = send_signal(*pid, escalation.first_signal, origin) {
+            errors.push(err);
+        }
+    }
+
+    let deadline = Instant::now() + escalation.grace;
+    let mut alive = targets.iter().copied().filter(|(pid, start_time)| is_same_alive(*pid, *start_time)).collect::<Vec<_>>();
+    while !alive.is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(100));
+        alive.retain(|(pid, start_time)| is_same_alive(*pid, *start_time));
+    }
+
+    let mut escalated = 0;
+    if let Some(final_signal) = escalation.final_signal {
+        for (pid, start_time) in alive.iter() {
+            if !is_same_alive(*pid, *start_time) {
+                continue;
+            }
+            match send_signal(*pid, final_signal, origin) {
+                Ok(()) => escalated += 1,
+                Err(err) => errors.push(err),
+            }
+        }
+        std::thread::sleep(Duration::from_millis(200));
+        alive.retain(|(pid, start_time)| is_same_alive(*pid, *start_time));
+    }
+    let alive = alive.iter().map(|(pid, _)| *pid).collect::<Vec<_>>();
+
+    let mut report = format!(
+        "{} de {} processos encerrados com {}",
+        pids.len() - alive.len(),
+        pids.len(),
+        signal_name(escalation.first_signal),
+    );
+    if escalated > 0 {
+        report.push_str(&format!(", {} escalados para {}", escalated, signal_name(escalation.final_signal.unwrap_or(libc::SIGKILL))));
+    }
+    if !alive.is_empty() {
+        report.push_str(&format!(". Ainda vivos: {:?}", alive));
+    }
+    if !errors.is_empty() {
+        report.push_str(&format!(". Erros: {}", errors.join("; ")));
+    }
+    report
+}
\ No newline at end of file
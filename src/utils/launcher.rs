@@ -0,0 +1,224 @@
+use std::fs;
+use std::io::{ BufRead, BufReader, Read };
+use std::os::unix::process::{ CommandExt, ExitStatusExt };
+use std::path::{ Path, PathBuf };
+use std::process::{ Command, Stdio };
+use std::time::{ Duration, Instant };
+
+use futures_channel::mpsc::UnboundedSender;
+
+use super::audit::AuditTarget;
+use super::cgroup::{ bytes_from_mib, cpu_max_from_percent, create_managed_group, move_process, process_cgroup, CgroupLimits, CGROUP_ROOT };
+use super::functions::now_secs;
+use super::protection::Origin;
+use super::state::read_state;
+
+pub const MAX_OUTPUT_LINES: usize = 2000;
+// How long the parent waits for the child to stop itself and wait to be moved.
+const PLACEMENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, PartialEq)]
+pub struct LaunchRequest {
+    pub command: String,
+    pub cwd: String,
+    pub env: String,
+    pub nice: String,
+    pub cpu_percent: String,
+    pub memory_mib: String,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct LaunchedTask {
+    pub id: usize,
+    pub pid: u32,
+    pub command: String,
+    pub started: u64,
+    pub cgroup: Option<PathBuf>,
+    pub output: Vec<String>,
+    pub exit_status: Option<String>,
+}
+
+pub enum LaunchUpdate {
+    Output { id: usize, line: String },
+    Exited { id: usize, status: String },
+}
+
+impl LaunchedTask {
+    pub fn apply(&mut self, update: LaunchUpdate) {
+        match update {
+            LaunchUpdate::Output { line, .. } => {
+                self.output.push(line);
+                if self.output.len() > MAX_OUTPUT_LINES {
+                    self.output.remove(0);
+                }
+            }
+            LaunchUpdate::Exited { status, .. } => self.exit_status = Some(status),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.exit_status.is_none()
+    }
+}
+
+impl LaunchUpdate {
+    pub fn id(&self) -> usize {
+        match self {
+            LaunchUpdate::Output { id, .. } => *id,
+            LaunchUpdate::Exited { id, .. } => *id,
+        }
+    }
+}
+
+// One KEY=VALUE per line; blank lines and lines without '=' are ignored.
+fn parse_env(env: &str) -> Vec<(String, String)> {
+    env.lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+// With limits the shell stops itself before running the command, is moved (by the helper if
+// needed) and only then continues, so nothing the command forks can start outside the group.
+// pre_exec can't be used for this: spawn() waits for the exec, so a stop there would hang us.
+const STOP_WRAPPER: &str = "kill -STOP $$; exec sh -c \"$1\"";
+
+fn place_child(pid: u32, dir: &Path) -> Result<(), String> {
+    let deadline = Instant::now() + PLACEMENT_TIMEOUT;
+    while read_state(pid) != 'T' {
+        if Instant::now() >= deadline {
+            return Err("o processo não parou a tempo para ser movido".to_string());
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    move_process(pid, dir, Origin::User { confirmed: false }).map_err(|err| err.to_string())?;
+    let cgroup = format!("/{}", dir.strip_prefix(CGROUP_ROOT).unwrap_or(dir).display());
+    if process_cgroup(pid).as_deref() != Some(cgroup.as_str()) {
+        return Err(format!("o processo não está em {}", cgroup));
+    }
+    unsafe { libc::kill(pid as libc::pid_t, libc::SIGCONT) };
+    Ok(())
+}
+
+fn forward_output<R: Read + Send + 'static>(id: usize, stream: R, prefix: &'static str, updates: UnboundedSender<LaunchUpdate>) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(stream).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            let _ = updates.unbounded_send(LaunchUpdate::Output { id, line: format!("{}{}", prefix, line) });
+        }
+    });
+}
+
+pub fn launch(id: usize, request: &LaunchRequest, updates: UnboundedSender<LaunchUpdate>) -> Result<LaunchedTask, String> {
+    let command_line = request.command.trim().to_string();
+    if command_line.is_empty() {
+        return Err("Informe um comando".to_string());
+    }
+    let nice = match request.nice.trim() {
+        "" => 0,
+        value => value.parse::<i32>().map_err(|_| format!("Nice inválido: {}", value))?,
+    };
+    let wants_cgroup = !request.cpu_percent.trim().is_empty() || !request.memory_mib.trim().is_empty();
+    let limits = match wants_cgroup {
+        true => Some(CgroupLimits {
+            cpu_max: cpu_max_from_percent(&request.cpu_percent)?,
+            memory_max: bytes_from_mib(&request.memory_mib)?,
+            memory_high: "max".to_string(),
+            io_max: String::new(),
+        }),
+        false => None,
+    };
+
+    let mut command = Command::new("sh");
+    match limits {
+        Some(_) => command.arg("-c").arg(STOP_WRAPPER).arg("task-manager").arg(&command_line),
+        None => command.arg("-c").arg(&command_line),
+    };
+    command
+        .envs(parse_env(&request.env))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if !request.cwd.trim().is_empty() {
+        command.current_dir(request.cwd.trim());
+    }
+    if nice != 0 {
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setpriority(libc::PRIO_PROCESS, 0, nice) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    // Unique across runs, since groups from an earlier session may still be around.
+    let cgroup = match limits {
+        Some(limits) => Some(
+            create_managed_group(&format!("launch-{}-{}-{}", std::process::id(), id, now_secs()))
+                .and_then(|dir| limits.write(&dir).map(|_| dir))
+                .map_err(|err| format!("Erro ao criar o cgroup: {}", err))?,
+        ),
+        None => None,
+    };
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            if let Some(dir) = &cgroup {
+                let _ = fs::remove_dir(dir);
+            }
+            return Err(format!("Erro ao executar {}: {}", command_line, err));
+        }
+    };
+    let pid = child.id();
+    if let Some(dir) = &cgroup {
+        if let Err(err) = place_child(pid, dir) {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = fs::remove_dir(dir);
+            return Err(format!("Limites de cgroup não aplicados, processo encerrado: {}", err));
+        }
+    }
+    // Built by hand: right after the fork /proc may still show our own command line.
+    AuditTarget { pid, name: "sh".to_string(), cmdline: format!("sh -c {}", command_line) }.record(
+        "iniciar processo",
+        &format!("cwd={} nice={} cpu={}% memória={} MiB", request.cwd.trim(), nice, request.cpu_percent.trim(), request.memory_mib.trim()),
+        "ok",
+    );
+
+    let task = LaunchedTask {
+        id,
+        pid,
+        command: command_line,
+        started: now_secs(),
+        cgroup: cgroup.clone(),
+        output: Vec::new(),
+        exit_status: None,
+    };
+
+    if let Some(stdout) = child.stdout.take() {
+        forward_output(id, stdout, "", updates.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_output(id, stderr, "[stderr] ", updates.clone());
+    }
+    std::thread::spawn(move || {
+        let status = match child.wait() {
+            Ok(status) => match (status.code(), status.signal()) {
+                (Some(code), _) => format!("saiu com código {}", code),
+                (None, Some(signal)) => format!("terminado pelo sinal {}", signal),
+                _ => "terminado".to_string(),
+            },
+            Err(err) => format!("erro ao aguardar: {}", err),
+        };
+        // Fails while something the task left behind still runs in the group; that one stays.
+        if let Some(dir) = cgroup {
+            let _ = fs::remove_dir(dir);
+        }
+        let _ = updates.unbounded_send(LaunchUpdate::Exited { id, status });
+    });
+    Ok(task)
+}
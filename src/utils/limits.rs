@@ -0,0 +1,184 @@
+use std::fs;
+use std::io;
+use std::sync::Mutex;
+use std::time::{ Duration, Instant };
+
+use super::protection::{ authorize, Origin };
+
+// Counting a user's threads walks all of /proc, and the details panel re-reads its limits on every render.
+const THREAD_COUNT_TTL: Duration = Duration::from_secs(5);
+static THREAD_COUNT: Mutex<Option<(String, Instant, u64)>> = Mutex::new(None);
+
+#[derive(Clone, PartialEq)]
+pub struct Limit {
+    pub name: String,
+    pub soft: String,
+    pub hard: String,
+    pub units: String,
+    pub usage: Option<String>,
+}
+
+pub fn read_limits(pid: u32) -> io::Result<Vec<Limit>> {
+    let content = fs::read_to_string(format!("/proc/{}/limits", pid))?;
+    let mut lines = content.lines();
+    let header = lines.next().unwrap_or_default();
+    let soft_at = header.find("Soft Limit").unwrap_or(26);
+    let hard_at = header.find("Hard Limit").unwrap_or(47);
+    let units_at = header.find("Units").unwrap_or(68);
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).unwrap_or_default();
+
+    let mut limits = Vec::new();
+    for line in lines {
+        let name = column(line, 0, soft_at);
+        let limit = Limit {
+            usage: current_usage(pid, &name, &status),
+            soft: column(line, soft_at, hard_at),
+            hard: column(line, hard_at, units_at),
+            units: column(line, units_at, line.len()),
+            name,
+        };
+        limits.push(limit);
+    }
+    Ok(limits)
+}
+
+fn column(line: &str, start: usize, end: usize) -> String {
+    line.get(start..end.min(line.len())).unwrap_or_default().trim().to_string()
+}
+
+fn current_usage(pid: u32, name: &str, status: &str) -> Option<String> {
+    match name {
+        "Max open files" => fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|fds| fds.count().to_string()),
+        "Max processes" => user_threads(status_field(status, "Uid")?.split_whitespace().next()?).map(|count| count.to_string()),
+        "Max stack size" => status_bytes(status, "VmStk"),
+        "Max address space" => status_bytes(status, "VmSize"),
+        "Max data size" => status_bytes(status, "VmData"),
+        "Max resident set" => status_bytes(status, "VmRSS"),
+        "Max locked memory" => status_bytes(status, "VmLck"),
+        "Max pending signals" => status_field(status, "SigQ")?.split('/').next().map(|queued| queued.to_string()),
+        _ => None,
+    }
+}
+
+fn status_field<'a>(status: &'a str, field: &str) -> Option<&'a str> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))
+        .map(|value| value.trim())
+}
+
+fn status_bytes(status: &str, field: &str) -> Option<String> {
+    let kib = status_field(status, field)?.trim_end_matches("kB").trim().parse::<u64>().ok()?;
+    Some((kib * 1024).to_string())
+}
+
+// RLIMIT_NPROC is checked against every thread owned by the real user id.
+fn user_threads(uid: &str) -> Option<u64> {
+    let mut cached = THREAD_COUNT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((owner, at, count)) = cached.as_ref() {
+        if owner.eq(uid) && at.elapsed() < THREAD_COUNT_TTL {
+            return Some(*count);
+        }
+    }
+    let count = count_user_threads(uid)?;
+    *cached = Some((uid.to_string(), Instant::now(), count));
+    Some(count)
+}
+
+fn count_user_threads(uid: &str) -> Option<u64> {
+    let mut count = 0;
+    for entry in fs::read_dir("/proc").ok()?.flatten() {
+        let Ok(status) = fs::read_to_string(entry.path().join("status")) else {
+            continue;
+        };
+        let owner = status_field(&status, "Uid").and_then(|ids| ids.split_whitespace().next());
+        if owner.eq(&Some(uid)) {
+            count += status_field(&status, "Threads").and_then(|threads| threads.parse::<u64>().ok()).unwrap_or(0);
+        }
+    }
+    Some(count)
+}
+
+fn resource(name: &str) -> Option<i32> {
+    let resource = match name {
+        "Max cpu time" => libc::RLIMIT_CPU,
+        "Max file size" => libc::RLIMIT_FSIZE,
+        "Max data size" => libc::RLIMIT_DATA,
+        "Max stack size" => libc::RLIMIT_STACK,
+        "Max core file size" => libc::RLIMIT_CORE,
+        "Max resident set" => libc::RLIMIT_RSS,
+        "Max processes" => libc::RLIMIT_NPROC,
+        "Max open files" => libc::RLIMIT_NOFILE,
+        "Max locked memory" => libc::RLIMIT_MEMLOCK,
+        "Max address space" => libc::RLIMIT_AS,
+        "Max file locks" => libc::RLIMIT_LOCKS,
+        "Max pending signals" => libc::RLIMIT_SIGPENDING,
+        "Max msgqueue size" => libc::RLIMIT_MSGQUEUE,
+        "Max nice priority" => libc::RLIMIT_NICE,
+        "Max realtime priority" => libc::RLIMIT_RTPRIO,
+        "Max realtime timeout" => libc::RLIMIT_RTTIME,
+        _ => return None,
+    };
+    Some(resource as i32)
+}
+
+// An empty side keeps its current value.
+fn parse_value(value: &str, current: u64) -> Result<u64, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(current);
+    }
+    if value.eq("unlimited") || value.eq("infinity") {
+        return Ok(libc::RLIM64_INFINITY);
+    }
+    value.parse::<u64>().map_err(|_| format!("Valor inválido: {}", value))
+}
+
+pub fn set_limit(pid: u32, name: &str, soft: &str, hard: &str, origin: Origin) -> Result<(), String> {
+    authorize(pid, origin)?;
+    let resource = resource(name).ok_or_else(|| format!("Limite desconhecido: {}", name))?;
+    if soft.trim().is_empty() && hard.trim().is_empty() {
+        return Err("Informe o soft, o hard ou os dois".to_string());
+    }
+    let mut current = libc::rlimit64 { rlim_cur: 0, rlim_max: 0 };
+    let result = unsafe { libc::prlimit64(pid as libc::pid_t, resource as _, std::ptr::null(), &mut current) };
+    if result != 0 {
+        return Err(format!("prlimit falhou: {}", io::Error::last_os_error()));
+    }
+    let new_limit = libc::rlimit64 {
+        rlim_cur: parse_value(soft, current.rlim_cur)?,
+        rlim_max: parse_value(hard, current.rlim_max)?,
+    };
+    let result = unsafe { libc::prlimit64(pid as libc::pid_t, resource as _, &new_limit, std::ptr::null_mut()) };
+    if result != 0 {
+        return Err(format!("prlimit falhou: {}", io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_limit_values() {
+        assert_eq!(parse_value(" 1024 ", 7), Ok(1024));
+        assert_eq!(parse_value("unlimited", 7), Ok(libc::RLIM64_INFINITY));
+        assert_eq!(parse_value("infinity", 7), Ok(libc::RLIM64_INFINITY));
+        assert!(parse_value("-1", 7).is_err());
+    }
+
+    #[test]
+    fn empty_value_keeps_the_current_one() {
+        assert_eq!(parse_value("", 4096), Ok(4096));
+        assert_eq!(parse_value("  ", libc::RLIM64_INFINITY), Ok(libc::RLIM64_INFINITY));
+    }
+
+    #[test]
+    fn finds_columns_by_header() {
+        let limits = read_limits(std::process::id()).unwrap();
+        let files = limits.iter().find(|limit| limit.name.eq("Max open files")).unwrap();
+        assert!(files.units.eq("files") && files.usage.is_some());
+        assert!(limits.iter().all(|limit| resource(&limit.name).is_some()));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::fs;
+
+pub const NAMESPACE_KINDS: [&str; 7] = ["pid", "net", "mnt", "uts", "ipc", "user", "cgroup"];
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Namespaces {
+    pub pid: u64,
+    pub net: u64,
+    pub mnt: u64,
+    pub uts: u64,
+    pub ipc: u64,
+    pub user: u64,
+    pub cgroup: u64,
+}
+
+impl Namespaces {
+    pub fn read(pid: u32) -> Namespaces {
+        Namespaces {
+            pid: read_inode(pid, "pid"),
+            net: read_inode(pid, "net"),
+            mnt: read_inode(pid, "mnt"),
+            uts: read_inode(pid, "uts"),
+            ipc: read_inode(pid, "ipc"),
+            user: read_inode(pid, "user"),
+            cgroup: read_inode(pid, "cgroup"),
+        }
+    }
+
+    pub fn get(&self, kind: &str) -> u64 {
+        match kind {
+            "pid" => self.pid,
+            "net" => self.net,
+            "mnt" => self.mnt,
+            "uts" => self.uts,
+            "ipc" => self.ipc,
+            "user" => self.user,
+            "cgroup" => self.cgroup,
+            _ => 0,
+        }
+    }
+}
+
+// The link target looks like "net:[4026531840]"; 0 means it could not be read (usually permissions).
+fn read_inode(pid: u32, kind: &str) -> u64 {
+    let Ok(target) = fs::read_link(format!("/proc/{}/ns/{}", pid, kind)) else {
+        return 0;
+    };
+    let target = target.to_string_lossy();
+    target
+        .split_once('[')
+        .and_then(|(_, rest)| rest.strip_suffix(']'))
+        .and_then(|inode| inode.parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn format_inode(inode: u64) -> String {
+    if inode == 0 {
+        return "-".to_string();
+    }
+    inode.to_string()
+}
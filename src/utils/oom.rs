@@ -0,0 +1,40 @@
+use std::fs;
+
+use super::app_props::MyProcess;
+use super::helper::perform;
+use super::helper_protocol::Request;
+use super::protection::Origin;
+
+pub const OOM_CANDIDATES: usize = 5;
+
+pub fn read_oom_score(pid: u32) -> i32 {
+    read_value(pid, "oom_score")
+}
+
+pub fn read_oom_score_adj(pid: u32) -> i32 {
+    read_value(pid, "oom_score_adj")
+}
+
+fn read_value(pid: u32, file: &str) -> i32 {
+    fs::read_to_string(format!("/proc/{}/{}", pid, file))
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn set_oom_score_adj(pid: u32, value: &str, origin: Origin) -> Result<i32, String> {
+    let adj = value.trim().parse::<i32>().map_err(|_| format!("Valor inválido: {}", value))?;
+    if !(-1000..=1000).contains(&adj) {
+        return Err("oom_score_adj deve estar entre -1000 e 1000".to_string());
+    }
+    perform(Request::OomScoreAdj { pid, value: adj }, origin).map_err(|err| format!("Erro ao alterar oom_score_adj: {}", err))?;
+    Ok(adj)
+}
+
+// The kernel picks the highest oom_score first; ties don't matter here.
+pub fn oom_candidates(procs: &[MyProcess]) -> Vec<MyProcess> {
+    let mut candidates = procs.iter().filter(|proc| proc.oom_score > 0).cloned().collect::<Vec<_>>();
+    candidates.sort_by_key(|proc| std::cmp::Reverse(proc.oom_score));
+    candidates.truncate(OOM_CANDIDATES);
+    candidates
+}
\ No newline at end of file
@@ -0,0 +1,251 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use super::functions::{ read_number, read_trimmed };
+
+pub const POWER_SUPPLY_ROOT: &str = "/sys/class/power_supply";
+// Discharge samples kept for the estimate, and the shortest span worth fitting a slope to.
+const ESTIMATE_WINDOW: u64 = 600;
+const MIN_ESTIMATE_SPAN: u64 = 30;
+
+#[derive(Clone, PartialEq)]
+pub struct Battery {
+    pub name: String,
+    pub status: String,
+    pub capacity: Option<f64>,
+    pub energy_wh: Option<f64>,
+    pub energy_full_wh: Option<f64>,
+    pub energy_design_wh: Option<f64>,
+    pub power_w: Option<f64>,
+    pub cycle_count: Option<u64>,
+    pub remaining_secs: Option<u64>,
+    pub power_history: Vec<f64>,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct PowerStatus {
+    pub ac_online: Option<bool>,
+    pub batteries: Vec<Battery>,
+}
+
+// Energy readings while discharging, per battery, reset whenever it starts charging.
+#[derive(Default)]
+pub struct DischargeHistory {
+    samples: Vec<(String, u64, f64)>,
+}
+
+impl Battery {
+    // Drivers report either energy (µWh, µW) or charge (µAh, µA); charge is converted with voltage_now.
+    fn read(dir: &Path, name: &str) -> Battery {
+        let value = |file: &str| read_number::<f64>(&dir.join(file));
+        let voltage = value("voltage_now").map(|microvolts| microvolts / 1e6);
+        let energy = |energy_file: &str, charge_file: &str| {
+            value(energy_file).map(|microwatt_hours| microwatt_hours / 1e6)
+                .or_else(|| Some(value(charge_file)? / 1e6 * voltage?))
+        };
+        let energy_wh = energy("energy_now", "charge_now");
+        let energy_full_wh = energy("energy_full", "charge_full");
+        Battery {
+            name: name.to_string(),
+            status: read_trimmed(&dir.join("status")).unwrap_or_else(|| "Unknown".to_string()),
+            capacity: value("capacity").or_else(|| Some(energy_wh? / energy_full_wh? * 100.0)),
+            energy_wh,
+            energy_full_wh,
+            energy_design_wh: energy("energy_full_design", "charge_full_design"),
+            power_w: value("power_now").map(|microwatts| microwatts / 1e6)
+                .or_else(|| Some(value("current_now")? / 1e6 * voltage?))
+                .map(f64::abs),
+            cycle_count: value("cycle_count").map(|cycles| cycles as u64).filter(|cycles| *cycles > 0),
+            remaining_secs: None,
+            power_history: Vec::new(),
+        }
+    }
+
+    pub fn is_discharging(&self) -> bool {
+        self.status.eq("Discharging")
+    }
+
+    // Full capacity now against the design capacity.
+    pub fn health(&self) -> Option<f64> {
+        Some(self.energy_full_wh? / self.energy_design_wh.filter(|design| *design > 0.0)? * 100.0)
+    }
+
+    pub fn status_label(&self) -> &str {
+        match self.status.as_str() {
+            "Discharging" => "Descarregando",
+            "Charging" => "Carregando",
+            "Full" => "Carregada",
+            "Not charging" => "Sem carregar",
+            _ => "Desconhecido",
+        }
+    }
+}
+
+pub fn read_power(root: &Path) -> PowerStatus {
+    let mut status = PowerStatus::default();
+    let Ok(entries) = fs::read_dir(root) else {
+        return status;
+    };
+    let mut dirs = entries.flatten().map(|entry| entry.path()).collect::<Vec<PathBuf>>();
+    dirs.sort();
+    for dir in dirs {
+        let name = dir.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        // Mice, keyboards and headsets report their own batteries; they don't power the system.
+        if read_trimmed(&dir.join("scope")).as_deref() == Some("Device") {
+            continue;
+        }
+        match read_trimmed(&dir.join("type")).as_deref() {
+            Some("Battery") if read_trimmed(&dir.join("present")).as_deref() != Some("0") => status.batteries.push(Battery::read(&dir, &name)),
+            Some("Mains") | Some("USB") => {
+                let online = read_trimmed(&dir.join("online")).as_deref() == Some("1");
+                status.ac_online = Some(status.ac_online.unwrap_or(false) || online);
+            }
+            _ => {}
+        }
+    }
+    status
+}
+
+impl DischargeHistory {
+    // Records the new readings and fills in remaining_secs on each battery.
+    pub fn update(&mut self, status: &mut PowerStatus, now: u64) {
+        for battery in status.batteries.iter_mut() {
+            if !battery.is_discharging() {
+                self.samples.retain(|(name, _, _)| name.ne(&battery.name));
+            } else if let Some(energy) = battery.energy_wh {
+                self.samples.push((battery.name.clone(), now, energy));
+            }
+            battery.remaining_secs = self.estimate(battery, now);
+        }
+        self.samples.retain(|(_, at, _)| now.saturating_sub(*at) <= ESTIMATE_WINDOW);
+    }
+
+    // Least-squares slope of energy over time; the instantaneous power_now is very noisy on most laptops.
+    fn estimate(&self, battery: &Battery, now: u64) -> Option<u64> {
+        let energy = battery.energy_wh?;
+        if !battery.is_discharging() {
+            return match battery.status.as_str() {
+                "Charging" => Some(((battery.energy_full_wh? - energy).max(0.0) / battery.power_w.filter(|power| *power > 0.0)? * 3600.0) as u64),
+                _ => None,
+            };
+        }
+        let samples = self.samples.iter().filter(|(name, _, _)| name.eq(&battery.name)).map(|(_, at, energy)| (*at as f64, *energy)).collect::<Vec<_>>();
+        let span = samples.first().map(|(first, _)| now as f64 - first).unwrap_or(0.0);
+        let rate = match span >= MIN_ESTIMATE_SPAN as f64 && samples.len() >= 2 {
+            true => {
+                let count = samples.len() as f64;
+                let mean_t = samples.iter().map(|(at, _)| at).sum::<f64>() / count;
+                let mean_e = samples.iter().map(|(_, energy)| energy).sum::<f64>() / count;
+                let covariance = samples.iter().map(|(at, energy)| (at - mean_t) * (energy - mean_e)).sum::<f64>();
+                let variance = samples.iter().map(|(at, _)| (at - mean_t).powi(2)).sum::<f64>();
+                // Wh per second times 3600, i.e. watts, positive while draining.
+                -covariance / variance * 3600.0
+            }
+            false => battery.power_w?,
+        };
+        match rate > 0.0 {
+            true => Some((energy / rate * 3600.0) as u64),
+            false => None,
+        }
+    }
+}
+
+pub fn format_duration(secs: u64) -> String {
+    format!("{}h{:02}", secs / 3600, secs % 3600 / 60)
+}
+
+// One line for the process table's toolbar.
+pub fn power_summary(status: &PowerStatus) -> Option<String> {
+    let battery = status.batteries.first()?;
+    let mut summary = format!("Bateria {:.0}% - {}", battery.capacity.unwrap_or(0.0), battery.status_label().to_lowercase());
+    if let Some(power) = battery.power_w.filter(|power| *power > 0.0) {
+        summary.push_str(&format!(" a {:.1} W", power));
+    }
+    if let Some(remaining) = battery.remaining_secs {
+        summary.push_str(&format!(", ~{} restantes", format_duration(remaining)));
+    }
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> PowerStatus {
+        read_power(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/power_supply"))
+    }
+
+    fn discharging(energy_wh: f64, power_w: f64) -> PowerStatus {
+        let mut status = fixture();
+        status.batteries.truncate(1);
+        status.batteries[0].energy_wh = Some(energy_wh);
+        status.batteries[0].power_w = Some(power_w);
+        status
+    }
+
+    #[test]
+    fn reads_system_batteries_only() {
+        let status = fixture();
+        assert_eq!(status.ac_online, Some(false));
+        let names = status.batteries.iter().map(|battery| battery.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["BAT0", "BAT1"]);
+    }
+
+    #[test]
+    fn reads_energy_based_batteries() {
+        let battery = &fixture().batteries[0];
+        assert_eq!((battery.energy_wh, battery.energy_full_wh, battery.power_w), (Some(40.0), Some(50.0), Some(12.0)));
+        assert_eq!((battery.capacity, battery.cycle_count), (Some(80.0), Some(312)));
+        assert!((battery.health().unwrap() - 87.72).abs() < 0.01);
+    }
+
+    #[test]
+    fn converts_charge_with_voltage() {
+        let battery = &fixture().batteries[1];
+        assert_eq!((battery.energy_wh, battery.energy_full_wh, battery.power_w), (Some(24.0), Some(48.0), Some(12.0)));
+        // No capacity file, and a zero cycle count means the driver doesn't know.
+        assert_eq!((battery.capacity, battery.cycle_count), (Some(50.0), None));
+    }
+
+    #[test]
+    fn estimates_charging_time_from_power() {
+        let mut status = fixture();
+        DischargeHistory::default().update(&mut status, 0);
+        assert_eq!(status.batteries[1].remaining_secs, Some(7200));
+    }
+
+    #[test]
+    fn uses_power_now_until_there_is_enough_history() {
+        let mut history = DischargeHistory::default();
+        let mut status = discharging(40.0, 20.0);
+        history.update(&mut status, 0);
+        assert_eq!(status.batteries[0].remaining_secs, Some(7200));
+    }
+
+    #[test]
+    fn fits_the_drain_rate_once_the_span_is_long_enough() {
+        let mut history = DischargeHistory::default();
+        let mut remaining = None;
+        // Drains 36 Wh/h (0.01 Wh/s) while power_now claims 100 W.
+        for second in (0..=60).step_by(10) {
+            let mut status = discharging(40.0 - 0.01 * second as f64, 100.0);
+            history.update(&mut status, second);
+            remaining = status.batteries[0].remaining_secs;
+        }
+        assert_eq!(remaining, Some(3940));
+    }
+
+    #[test]
+    fn forgets_the_history_when_charging_starts() {
+        let mut history = DischargeHistory::default();
+        for second in (0..=60).step_by(10) {
+            history.update(&mut discharging(40.0 - 0.01 * second as f64, 100.0), second);
+        }
+        let mut status = discharging(39.4, 100.0);
+        status.batteries[0].status = "Charging".to_string();
+        history.update(&mut status, 70);
+        let mut status = discharging(39.4, 100.0);
+        history.update(&mut status, 80);
+        assert_eq!(status.batteries[0].remaining_secs, Some(1418));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,38 @@
+use std::fs;
+
+use super::helper::perform;
+use super::helper_protocol::{ parse_cpu_list, Request };
+use super::protection::Origin;
+
+pub const IONICE_CLASSES: [(&str, i32); 3] = [("Tempo real", 1), ("Melhor esforço", 2), ("Ocioso", 3)];
+
+pub fn read_nice(pid: u32) -> Option<i32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // Field 19 of /proc/<pid>/stat, counting the fields after the command name.
+    stat.rsplit_once(')')?.1.split_whitespace().nth(16)?.parse().ok()
+}
+
+pub fn read_affinity(pid: u32) -> Option<String> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status.lines().find_map(|line| line.strip_prefix("Cpus_allowed_list:")).map(|list| list.trim().to_string())
+}
+
+pub fn renice(pid: u32, nice: i32, origin: Origin) -> Result<(), String> {
+    if !(-20..=19).contains(&nice) {
+        return Err(format!("Nice inválido: {} (use -20 a 19)", nice));
+    }
+    perform(Request::Renice { pid, nice }, origin).map_err(|err| format!("Erro ao alterar a prioridade de {}: {}", pid, err))
+}
+
+pub fn set_ionice(pid: u32, class: i32, level: &str, origin: Origin) -> Result<(), String> {
+    let level = match level.trim() {
+        "" => 4,
+        level => level.parse::<i32>().map_err(|_| format!("Nível inválido: {}", level))?,
+    };
+    perform(Request::Ionice { pid, class, level }, origin).map_err(|err| format!("Erro ao alterar a prioridade de E/S de {}: {}", pid, err))
+}
+
+pub fn set_affinity(pid: u32, list: &str, origin: Origin) -> Result<(), String> {
+    let cpus = parse_cpu_list(list)?;
+    perform(Request::Affinity { pid, cpus }, origin).map_err(|err| format!("Erro ao alterar a afinidade de {}: {}", pid, err))
+}
\ No newline at end of file
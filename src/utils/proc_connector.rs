@@ -0,0 +1,244 @@
+use std::collections::{ HashMap, HashSet };
+use std::fs;
+use std::io;
+use std::mem;
+use std::os::fd::{ AsRawFd, FromRawFd, OwnedFd };
+
+use super::events::{ EventKind, ProcessEvent };
+use super::functions::now_secs;
+use super::helper_protocol::stat_field;
+
+// From linux/netlink.h, linux/connector.h and linux/cn_proc.h.
+const NETLINK_CONNECTOR: libc::c_int = 11;
+const NLMSG_DONE: u16 = 3;
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+const PROC_EVENT_FORK: u32 = 0x00000001;
+const PROC_EVENT_EXEC: u32 = 0x00000002;
+const PROC_EVENT_EXIT: u32 = 0x80000000;
+const NLMSG_HEADER_LEN: usize = 16;
+const CN_MSG_LEN: usize = 20;
+// proc_event starts with what, cpu and timestamp_ns before the per-event data.
+const EVENT_DATA_OFFSET: usize = NLMSG_HEADER_LEN + CN_MSG_LEN + 16;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ConnectorEvent {
+    Fork { pid: u32, parent: u32 },
+    Exec { pid: u32 },
+    Exit { pid: u32, status: u32 },
+}
+
+pub struct ProcConnector {
+    socket: OwnedFd,
+}
+
+impl ProcConnector {
+    // Needs CAP_NET_ADMIN on most kernels; callers fall back to polling on error.
+    pub fn open() -> io::Result<ProcConnector> {
+        let fd = unsafe { libc::socket(libc::AF_NETLINK, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, NETLINK_CONNECTOR) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        let mut address: libc::sockaddr_nl = unsafe { mem::zeroed() };
+        address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        address.nl_pid = 0;
+        address.nl_groups = CN_IDX_PROC;
+        let result = unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let connector = ProcConnector { socket };
+        connector.send_listen()?;
+        Ok(connector)
+    }
+
+    fn send_listen(&self) -> io::Result<()> {
+        let total_len = NLMSG_HEADER_LEN + CN_MSG_LEN + 4;
+        let mut message = Vec::with_capacity(total_len);
+        message.extend_from_slice(&(total_len as u32).to_ne_bytes());
+        message.extend_from_slice(&NLMSG_DONE.to_ne_bytes());
+        message.extend_from_slice(&0u16.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&std::process::id().to_ne_bytes());
+        message.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+        message.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&0u32.to_ne_bytes());
+        message.extend_from_slice(&4u16.to_ne_bytes());
+        message.extend_from_slice(&0u16.to_ne_bytes());
+        message.extend_from_slice(&PROC_CN_MCAST_LISTEN.to_ne_bytes());
+        let sent = unsafe { libc::send(self.socket.as_raw_fd(), message.as_ptr() as *const libc::c_void, message.len(), 0) };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn receive(&self) -> io::Result<Vec<ConnectorEvent>> {
+        let mut buffer = [0u8; 4096];
+        let received = unsafe { libc::recv(self.socket.as_raw_fd(), buffer.as_mut_ptr() as *mut libc::c_void, buffer.len(), 0) };
+        if received < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(parse_messages(&buffer[..received as usize]))
+    }
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buffer.get(offset..offset + 4)?;
+    Some(u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+// Thread events (pid != tgid) are dropped, only whole processes are reported.
+fn parse_messages(buffer: &[u8]) -> Vec<ConnectorEvent> {
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while let Some(len) = read_u32(buffer, offset) {
+        let len = len as usize;
+        if len < NLMSG_HEADER_LEN || offset + len > buffer.len() {
+            break;
+        }
+        let message = &buffer[offset..offset + len];
+        let data = EVENT_DATA_OFFSET;
+        let event = match read_u32(message, NLMSG_HEADER_LEN + CN_MSG_LEN) {
+            Some(PROC_EVENT_FORK) => match (read_u32(message, data + 4), read_u32(message, data + 8), read_u32(message, data + 12)) {
+                (Some(parent), Some(pid), Some(tgid)) if pid == tgid => Some(ConnectorEvent::Fork { pid, parent }),
+                _ => None,
+            },
+            Some(PROC_EVENT_EXEC) => read_u32(message, data + 4).map(|pid| ConnectorEvent::Exec { pid }),
+            Some(PROC_EVENT_EXIT) => match (read_u32(message, data), read_u32(message, data + 4), read_u32(message, data + 8)) {
+                (Some(pid), Some(tgid), Some(status)) if pid == tgid => Some(ConnectorEvent::Exit { pid, status }),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(event) = event {
+            events.push(event);
+        }
+        offset += (len + 3) & !3;
+    }
+    events
+}
+
+struct Tracked {
+    name: String,
+    cmd: String,
+    parent: Option<u32>,
+    started: u64,
+}
+
+// Turns raw connector events into the same ProcessEvent records the polling diff produces.
+pub struct ConnectorTracker {
+    processes: HashMap<u32, Tracked>,
+    // Already running when listening started, so there is no fork event to expect for them.
+    preexisting: HashSet<u32>,
+}
+
+impl ConnectorTracker {
+    pub fn new() -> ConnectorTracker {
+        ConnectorTracker { processes: HashMap::new(), preexisting: live_pids() }
+    }
+
+    // After the socket overflowed (ENOBUFS) some events are gone; a /proc scan fills in what changed meanwhile.
+    pub fn resync(&mut self) -> Vec<ProcessEvent> {
+        let now = now_secs();
+        let live = live_pids();
+        let mut events = Vec::new();
+        let exited = self.processes.keys().filter(|pid| !live.contains(pid)).copied().collect::<Vec<_>>();
+        for pid in exited {
+            if let Some(tracked) = self.processes.remove(&pid) {
+                events.push(ProcessEvent {
+                    kind: EventKind::Exited,
+                    timestamp: now,
+                    pid,
+                    name: tracked.name,
+                    cmd: tracked.cmd,
+                    parent: tracked.parent,
+                    lifetime: Some(now.saturating_sub(tracked.started)),
+                    exit_status: None,
+                });
+            }
+        }
+        self.preexisting.retain(|pid| live.contains(pid));
+        let started = live.iter().filter(|pid| !self.processes.contains_key(pid) && !self.preexisting.contains(pid)).copied().collect::<Vec<_>>();
+        for pid in started {
+            let parent = stat_field(pid, 1).map(|parent| parent as u32);
+            let name = read_comm(pid).unwrap_or_default();
+            let cmd = read_cmdline(pid).unwrap_or_default();
+            self.processes.insert(pid, Tracked { name: name.clone(), cmd: cmd.clone(), parent, started: now });
+            events.push(ProcessEvent { kind: EventKind::Started, timestamp: now, pid, name, cmd, parent, lifetime: None, exit_status: None });
+        }
+        events
+    }
+
+    pub fn handle(&mut self, event: ConnectorEvent) -> Option<ProcessEvent> {
+        let now = now_secs();
+        match event {
+            ConnectorEvent::Fork { pid, parent } => {
+                let name = read_comm(pid).unwrap_or_default();
+                let cmd = read_cmdline(pid).unwrap_or_default();
+                self.processes.insert(pid, Tracked { name: name.clone(), cmd: cmd.clone(), parent: Some(parent), started: now });
+                Some(ProcessEvent { kind: EventKind::Started, timestamp: now, pid, name, cmd, parent: Some(parent), lifetime: None, exit_status: None })
+            }
+            ConnectorEvent::Exec { pid } => {
+                if let Some(tracked) = self.processes.get_mut(&pid) {
+                    tracked.name = read_comm(pid).unwrap_or_else(|| tracked.name.clone());
+                    tracked.cmd = read_cmdline(pid).unwrap_or_else(|| tracked.cmd.clone());
+                }
+                None
+            }
+            ConnectorEvent::Exit { pid, status } => {
+                self.preexisting.remove(&pid);
+                let event = match self.processes.remove(&pid) {
+                    Some(tracked) => ProcessEvent {
+                        kind: EventKind::Exited,
+                        timestamp: now,
+                        pid,
+                        name: tracked.name,
+                        cmd: tracked.cmd,
+                        parent: tracked.parent,
+                        lifetime: Some(now.saturating_sub(tracked.started)),
+                        exit_status: Some(status),
+                    },
+                    // Started before we were listening; the zombie's /proc entry usually still has its name.
+                    None => ProcessEvent {
+                        kind: EventKind::Exited,
+                        timestamp: now,
+                        pid,
+                        name: read_comm(pid).unwrap_or_else(|| "?".to_string()),
+                        cmd: read_cmdline(pid).unwrap_or_default(),
+                        parent: None,
+                        lifetime: None,
+                        exit_status: Some(status),
+                    },
+                };
+                Some(event)
+            }
+        }
+    }
+}
+
+fn live_pids() -> HashSet<u32> {
+    fs::read_dir("/proc")
+        .map(|entries| entries.flatten().filter_map(|entry| entry.file_name().to_str()?.parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+pub fn read_comm(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{}/comm", pid)).ok().map(|comm| comm.trim().to_string())
+}
+
+pub fn read_cmdline(pid: u32) -> Option<String> {
+    let cmdline = fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+    Some(cmdline.split(|byte| *byte == 0).filter(|arg| !arg.is_empty()).map(|arg| String::from_utf8_lossy(arg)).collect::<Vec<_>>().join(" "))
+}
\ No newline at end of file
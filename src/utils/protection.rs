@@ -0,0 +1,201 @@
+use std::fs;
+use std::sync::Mutex;
+
+use super::helper_protocol::process_cgroup;
+use super::settings::Settings;
+
+// The guard every action is checked against, kept in sync with the settings by the UI.
+static ACTIVE_GUARD: Mutex<Option<Guard>> = Mutex::new(None);
+
+pub const DEFAULT_PROTECTED: [&str; 7] = ["pid:1", "self", "name:Xorg", "name:Xwayland", "name:gnome-shell", "name:kwin_wayland", "name:sshd"];
+
+#[derive(Clone, PartialEq)]
+pub enum Protected {
+    Pid(u32),
+    Name(String),
+    User(u32),
+    Cgroup(String),
+    SelfProcess,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProtectionMode {
+    Block,
+    Confirm,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Verdict {
+    Allowed,
+    ReadOnly,
+    Blocked(String),
+    NeedsConfirmation(String),
+}
+
+// Who asked for an action: decides whether a protected target may be touched and whether the helper may be used.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Origin {
+    // A click in the UI; confirmed once the user ticked the checkbox for protected targets.
+    User { confirmed: bool },
+    // Watchdog rules and anything else nobody is watching.
+    Automatic,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Guard {
+    pub read_only: bool,
+    pub mode: ProtectionMode,
+    pub protected: Vec<Protected>,
+}
+
+impl ProtectionMode {
+    pub fn parse(value: &str) -> ProtectionMode {
+        match value {
+            "confirm" => ProtectionMode::Confirm,
+            _ => ProtectionMode::Block,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtectionMode::Block => "block",
+            ProtectionMode::Confirm => "confirm",
+        }
+    }
+}
+
+impl Protected {
+    // "pid:<n>", "name:<comm>", "user:<name or uid>", "cgroup:<path prefix>" or "self".
+    pub fn parse(text: &str) -> Result<Protected, String> {
+        let text = text.trim();
+        if text.eq("self") {
+            return Ok(Protected::SelfProcess);
+        }
+        match text.split_once(':') {
+            Some(("pid", pid)) => pid.trim().parse().map(Protected::Pid).map_err(|_| format!("PID inválido: {}", pid)),
+            Some(("name", name)) => Ok(Protected::Name(name.trim().to_string())),
+            Some(("user", user)) => match user.trim().parse::<u32>() {
+                Ok(uid) => Ok(Protected::User(uid)),
+                Err(_) => lookup_uid(user.trim()).map(Protected::User).ok_or_else(|| format!("usuário desconhecido: {}", user)),
+            },
+            Some(("cgroup", cgroup)) => Ok(Protected::Cgroup(cgroup.trim().to_string())),
+            _ => Err(format!("entrada inválida: {} (use pid:, name:, user:, cgroup: ou self)", text)),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Protected::Pid(pid) => format!("pid:{}", pid),
+            Protected::Name(name) => format!("name:{}", name),
+            Protected::User(uid) => format!("user:{}", uid),
+            Protected::Cgroup(cgroup) => format!("cgroup:{}", cgroup),
+            Protected::SelfProcess => "self".to_string(),
+        }
+    }
+
+    fn matches(&self, pid: u32) -> bool {
+        match self {
+            Protected::Pid(protected) => *protected == pid,
+            Protected::SelfProcess => pid == std::process::id(),
+            Protected::Name(name) => fs::read_to_string(format!("/proc/{}/comm", pid)).map(|comm| comm.trim().eq(name)).unwrap_or(false),
+            Protected::User(uid) => read_uid(pid) == Some(*uid),
+            Protected::Cgroup(prefix) => process_cgroup(pid).map(|cgroup| cgroup.starts_with(prefix.as_str())).unwrap_or(false),
+        }
+    }
+}
+
+pub fn parse_protected(lines: &[String]) -> (Vec<Protected>, Vec<String>) {
+    let mut protected = Vec::new();
+    let mut errors = Vec::new();
+    for line in lines.iter().filter(|line| !line.trim().is_empty() && !line.trim().starts_with('#')) {
+        match Protected::parse(line) {
+            Ok(entry) => protected.push(entry),
+            Err(err) => errors.push(err),
+        }
+    }
+    (protected, errors)
+}
+
+impl Guard {
+    pub fn new(settings: &Settings) -> Guard {
+        Guard {
+            read_only: settings.read_only,
+            mode: ProtectionMode::parse(&settings.protection_mode),
+            protected: parse_protected(&settings.protected).0,
+        }
+    }
+
+    pub fn check(&self, pid: u32) -> Verdict {
+        if self.read_only {
+            return Verdict::ReadOnly;
+        }
+        match self.protected.iter().find(|entry| entry.matches(pid)) {
+            None => Verdict::Allowed,
+            Some(entry) => {
+                let reason = format!("PID {} protegido por {}", pid, entry.describe());
+                match self.mode {
+                    ProtectionMode::Block => Verdict::Blocked(reason),
+                    ProtectionMode::Confirm => Verdict::NeedsConfirmation(reason),
+                }
+            }
+        }
+    }
+
+    // Unattended callers never get past a confirmation.
+    pub fn authorize(&self, pid: u32, origin: Origin) -> Result<(), String> {
+        match (self.check(pid), origin) {
+            (Verdict::NeedsConfirmation(_), Origin::User { confirmed: true }) => Ok(()),
+            (verdict, _) => verdict.refusal().map_or(Ok(()), Err),
+        }
+    }
+
+    // The strictest verdict over a set of targets, e.g. a whole process tree.
+    pub fn check_all(&self, pids: &[u32]) -> Verdict {
+        let verdicts = pids.iter().map(|pid| self.check(*pid)).collect::<Vec<_>>();
+        verdicts.iter().find(|verdict| matches!(verdict, Verdict::ReadOnly | Verdict::Blocked(_)))
+            .or_else(|| verdicts.iter().find(|verdict| matches!(verdict, Verdict::NeedsConfirmation(_))))
+            .cloned()
+            .unwrap_or(Verdict::Allowed)
+    }
+}
+
+impl Verdict {
+    // What an unattended caller (e.g. the watchdog) should do: anything but Allowed is refused.
+    pub fn refusal(&self) -> Option<String> {
+        match self {
+            Verdict::Allowed => None,
+            Verdict::ReadOnly => Some("modo somente leitura".to_string()),
+            Verdict::Blocked(reason) | Verdict::NeedsConfirmation(reason) => Some(reason.clone()),
+        }
+    }
+}
+
+pub fn set_active_guard(guard: Guard) {
+    *ACTIVE_GUARD.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(guard);
+}
+
+pub fn active_guard() -> Guard {
+    let active = ACTIVE_GUARD.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    active.unwrap_or_else(|| Guard::new(&Settings::load()))
+}
+
+// Checked by every action in utils, whoever calls it.
+pub fn authorize(pid: u32, origin: Origin) -> Result<(), String> {
+    active_guard().authorize(pid, origin)
+}
+
+fn read_uid(pid: u32) -> Option<u32> {
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status.lines().find_map(|line| line.strip_prefix("Uid:"))?.split_whitespace().next()?.parse().ok()
+}
+
+fn lookup_uid(user: &str) -> Option<u32> {
+    let passwd = fs::read_to_string("/etc/passwd").ok()?;
+    passwd.lines().find_map(|line| {
+        let fields = line.split(':').collect::<Vec<_>>();
+        match fields.as_slice() {
+            [name, _, uid, ..] if name.eq(&user) => uid.parse().ok(),
+            _ => None,
+        }
+    })
+}
\ No newline at end of file
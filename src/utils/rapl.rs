@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::PathBuf;
+
+use super::app_props::MyProcess;
+use super::functions::{ read_number, read_trimmed };
+
+pub const POWERCAP_ROOT: &str = "/sys/class/powercap";
+// Far above any single zone; a larger reading means the counter was reset (e.g. across suspend), not wrapped.
+const MAX_ZONE_WATTS: f64 = 2000.0;
+
+// One intel-rapl zone: "intel-rapl:0" is a package, "intel-rapl:0:1" one of its subzones (core, uncore, dram).
+#[derive(Clone, PartialEq)]
+pub struct RaplCounter {
+    pub id: String,
+    pub name: String,
+    pub energy_uj: u64,
+    pub max_energy_range_uj: u64,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct RaplPower {
+    pub id: String,
+    pub name: String,
+    pub watts: f64,
+    pub history: Vec<f64>,
+}
+
+pub struct RaplSource {
+    pub root: PathBuf,
+}
+
+// Last reading of each counter, to turn cumulative energy into power.
+#[derive(Default)]
+pub struct RaplMeter {
+    previous: Vec<RaplCounter>,
+}
+
+impl RaplSource {
+    pub fn default() -> RaplSource {
+        RaplSource { root: PathBuf::from(POWERCAP_ROOT) }
+    }
+
+    pub fn is_present(&self) -> bool {
+        fs::read_dir(&self.root)
+            .map(|entries| entries.flatten().any(|entry| entry.file_name().to_string_lossy().starts_with("intel-rapl:")))
+            .unwrap_or(false)
+    }
+
+    // energy_uj is root-only on most kernels since 5.10; unreadable zones are skipped.
+    pub fn read(&self) -> Vec<RaplCounter> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut counters = entries
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|id| id.starts_with("intel-rapl:"))
+            .filter_map(|id| {
+                let dir = self.root.join(&id);
+                Some(RaplCounter {
+                    name: read_trimmed(&dir.join("name")).unwrap_or_else(|| id.clone()),
+                    energy_uj: read_number(&dir.join("energy_uj"))?,
+                    max_energy_range_uj: read_number(&dir.join("max_energy_range_uj")).unwrap_or(0),
+                    id,
+                })
+            })
+            .collect::<Vec<_>>();
+        counters.sort_by(|first, second| first.id.cmp(&second.id));
+        counters
+    }
+}
+
+impl RaplPower {
+    // Every package has its own core and dram subzones, so those carry the zone number.
+    pub fn label(&self) -> String {
+        match self.id.matches(':').count() {
+            2 => format!("{} ({})", self.name, self.id.trim_start_matches("intel-rapl:")),
+            _ => self.name.clone(),
+        }
+    }
+
+    pub fn is_package(&self) -> bool {
+        self.name.starts_with("package")
+    }
+}
+
+// The counter goes up to max_energy_range_uj and then restarts from zero. Without a known
+// range, or with a reading past it, a drop can't be told apart from a reset and gives None.
+pub fn energy_delta(previous: u64, current: u64, max_energy_range_uj: u64) -> Option<u64> {
+    match current >= previous {
+        true => Some(current - previous),
+        false if max_energy_range_uj == 0 || previous > max_energy_range_uj => None,
+        false => Some(max_energy_range_uj - previous + current + 1),
+    }
+}
+
+impl RaplMeter {
+    // Power of each zone over the last `elapsed` seconds; empty on the first call.
+    // Zones whose counter can't be trusted this time are left out until the next sample.
+    pub fn sample(&mut self, counters: Vec<RaplCounter>, elapsed: f64) -> Vec<RaplPower> {
+        let powers = match elapsed > 0.0 {
+            true => counters
+                .iter()
+                .filter_map(|counter| {
+                    let previous = self.previous.iter().find(|previous| previous.id.eq(&counter.id))?;
+                    let joules = energy_delta(previous.energy_uj, counter.energy_uj, counter.max_energy_range_uj)? as f64 / 1e6;
+                    let watts = joules / elapsed;
+                    match watts <= MAX_ZONE_WATTS {
+                        true => Some(RaplPower { id: counter.id.clone(), name: counter.name.clone(), watts, history: Vec::new() }),
+                        false => None,
+                    }
+                })
+                .collect(),
+            false => Vec::new(),
+        };
+        self.previous = counters;
+        powers
+    }
+}
+
+// Splits the package power among processes by their share of the busy CPU time.
+// Idle and uncore power end up spread over whoever was running, so this is only a rough guide.
+pub fn process_shares(powers: &[RaplPower], procs: &[MyProcess]) -> Vec<(u32, String, f64)> {
+    let package_watts = powers.iter().filter(|power| power.is_package()).map(|power| power.watts).sum::<f64>();
+    let busy = procs.iter().map(|proc| proc.cpu_usage as f64).sum::<f64>();
+    if busy <= 0.0 {
+        return Vec::new();
+    }
+    let mut shares = procs
+        .iter()
+        .filter(|proc| proc.cpu_usage > 0.0)
+        .map(|proc| (proc.pid, proc.name.clone(), package_watts * proc.cpu_usage as f64 / busy))
+        .collect::<Vec<_>>();
+    shares.sort_by(|first, second| second.2.total_cmp(&first.2));
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    const RANGE: u64 = 262143328850;
+
+    fn counter(id: &str, energy_uj: u64, max_energy_range_uj: u64) -> RaplCounter {
+        RaplCounter { id: id.to_string(), name: "package-0".to_string(), energy_uj, max_energy_range_uj }
+    }
+
+    #[test]
+    fn reads_readable_zones_in_order() {
+        let source = RaplSource { root: Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/powercap") };
+        assert!(source.is_present());
+        let counters = source.read();
+        let ids = counters.iter().map(|counter| counter.id.as_str()).collect::<Vec<_>>();
+        // intel-rapl:1 has no energy_uj (as when it's root-only) and dtpm isn't RAPL.
+        assert_eq!(ids, vec!["intel-rapl:0", "intel-rapl:0:0", "intel-rapl:0:1", "intel-rapl:0:2"]);
+        assert_eq!((counters[0].name.as_str(), counters[0].energy_uj, counters[0].max_energy_range_uj), ("package-0", 262143000000, RANGE));
+        assert_eq!((counters[2].name.as_str(), counters[2].max_energy_range_uj), ("dram", 0));
+        // An empty name file falls back to the zone id.
+        assert_eq!(counters[3].name, "intel-rapl:0:2");
+    }
+
+    #[test]
+    fn delta_counts_through_the_wrap() {
+        assert_eq!(energy_delta(100, 250, RANGE), Some(150));
+        assert_eq!(energy_delta(RANGE, 0, RANGE), Some(1));
+        assert_eq!(energy_delta(RANGE - 10, 20, RANGE), Some(31));
+    }
+
+    #[test]
+    fn delta_is_unknown_without_a_usable_range() {
+        assert_eq!(energy_delta(500, 20, 0), None);
+        assert_eq!(energy_delta(RANGE + 5, 20, RANGE), None);
+    }
+
+    #[test]
+    fn sample_turns_energy_into_watts() {
+        let mut meter = RaplMeter::default();
+        assert!(meter.sample(vec![counter("intel-rapl:0", RANGE - 2_000_000, RANGE)], 1.0).is_empty());
+        // 5 J over 0.5 s across the wrap.
+        let powers = meter.sample(vec![counter("intel-rapl:0", 2_999_999, RANGE)], 0.5);
+        assert_eq!(powers.len(), 1);
+        assert!((powers[0].watts - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sample_skips_resets_and_unknown_ranges() {
+        let mut meter = RaplMeter::default();
+        meter.sample(vec![counter("intel-rapl:0", 200_000_000_000, RANGE), counter("intel-rapl:0:1", 9_000_000, 0)], 1.0);
+        // Both counters dropped, as after resume: one would read as ~62 kW, the other has no range.
+        let after_reset = vec![counter("intel-rapl:0", 1_000_000, RANGE), counter("intel-rapl:0:1", 1_000_000, 0)];
+        assert!(meter.sample(after_reset, 1.0).is_empty());
+        // The next sample starts from the reset values.
+        let powers = meter.sample(vec![counter("intel-rapl:0", 21_000_000, RANGE), counter("intel-rapl:0:1", 4_000_000, 0)], 1.0);
+        assert_eq!(powers.iter().map(|power| power.watts).collect::<Vec<_>>(), vec![20.0, 3.0]);
+    }
+
+    #[test]
+    fn sample_needs_elapsed_time() {
+        let mut meter = RaplMeter::default();
+        meter.sample(vec![counter("intel-rapl:0", 0, RANGE)], 1.0);
+        assert!(meter.sample(vec![counter("intel-rapl:0", 10, RANGE)], 0.0).is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStringExt;
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{ Command, Stdio };
+
+use super::helper_protocol::read_start_time;
+use super::kill_tree::{ is_same_alive, kill_tree, Escalation };
+use super::protection::Origin;
+
+#[derive(Clone, PartialEq)]
+pub struct LaunchContext {
+    pub pid: u32,
+    pub start_time: u64,
+    pub exe: PathBuf,
+    pub argv: Vec<OsString>,
+    pub cwd: PathBuf,
+    pub environ: Vec<(OsString, OsString)>,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+// Every entry ends in a NUL; empty arguments and values in between are kept as they are.
+fn split_nul(bytes: Vec<u8>) -> Vec<Vec<u8>> {
+    if bytes.is_empty() {
+        return Vec::new();
+    }
+    let bytes = bytes.strip_suffix(&[0]).unwrap_or(&bytes);
+    bytes.split(|byte| *byte == 0).map(|part| part.to_vec()).collect()
+}
+
+fn status_id(status: &str, field: &str) -> Option<u32> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field)?.strip_prefix(':'))
+        .and_then(|ids| ids.split_whitespace().next())
+        .and_then(|id| id.parse().ok())
+}
+
+impl LaunchContext {
+    pub fn capture(pid: u32) -> io::Result<LaunchContext> {
+        let argv = split_nul(fs::read(format!("/proc/{}/cmdline", pid))?)
+            .into_iter()
+            .map(OsString::from_vec)
+            .collect::<Vec<_>>();
+        if argv.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "processo sem linha de comando (thread do kernel ou zumbi)"));
+        }
+        let environ = split_nul(fs::read(format!("/proc/{}/environ", pid))?)
+            .into_iter()
+            .filter_map(|entry| {
+                let split = entry.iter().position(|byte| *byte == b'=')?;
+                Some((OsString::from_vec(entry[..split].to_vec()), OsString::from_vec(entry[split + 1..].to_vec())))
+            })
+            .collect();
+        let status = fs::read_to_string(format!("/proc/{}/status", pid))?;
+        let id = |field: &str| status_id(&status, field).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{} ilegível em /proc/{}/status", field, pid)));
+        Ok(LaunchContext {
+            pid,
+            start_time: read_start_time(pid).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "processo já terminou"))?,
+            // argv[0] is whatever the process chose to show ("postgres: writer", "-bash"), not a path to run.
+            exe: fs::read_link(format!("/proc/{}/exe", pid))?,
+            argv,
+            cwd: fs::read_link(format!("/proc/{}/cwd", pid))?,
+            environ,
+            uid: id("Uid")?,
+            gid: id("Gid")?,
+        })
+    }
+
+    pub fn command_line(&self) -> String {
+        self.argv.iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>().join(" ")
+    }
+
+    // The child is detached into its own session with setsid, so closing the task manager doesn't
+    // take it down, and a background thread waits on it so it never lingers as a zombie.
+    pub fn launch(&self) -> io::Result<u32> {
+        let mut command = Command::new(&self.exe);
+        command
+            .arg0(&self.argv[0])
+            .args(&self.argv[1..])
+            .current_dir(&self.cwd)
+            .env_clear()
+            .envs(self.environ.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if self.uid != unsafe { libc::geteuid() } {
+            command.uid(self.uid).gid(self.gid);
+        }
+        unsafe {
+            command.pre_exec(|| {
+                libc::setsid();
+                Ok(())
+            });
+        }
+        let mut child = command.spawn()?;
+        let pid = child.id();
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(pid)
+    }
+}
+
+pub fn restart(pid: u32, origin: Origin) -> Result<String, String> {
+    let context = LaunchContext::capture(pid).map_err(|err| format!("Erro ao ler o contexto do processo: {}", err))?;
+    let report = kill_tree(&[pid], Escalation::default(), origin);
+    if is_same_alive(context.pid, context.start_time) {
+        return Err(format!("O processo não terminou: {}", report));
+    }
+    let new_pid = context.launch().map_err(|err| format!("Erro ao reiniciar {}: {}", context.command_line(), err))?;
+    Ok(format!("{} reiniciado como PID {}", context.command_line(), new_pid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_empty_arguments() {
+        let parts = split_nul(b"git\0commit\0-m\0\0".to_vec());
+        assert_eq!(parts, vec![b"git".to_vec(), b"commit".to_vec(), b"-m".to_vec(), Vec::new()]);
+        assert_eq!(split_nul(b"EMPTY=\0PATH=/bin\0".to_vec()), vec![b"EMPTY=".to_vec(), b"PATH=/bin".to_vec()]);
+        assert!(split_nul(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn captures_the_executable_and_ids() {
+        let context = LaunchContext::capture(std::process::id()).unwrap();
+        assert_eq!(context.exe, std::env::current_exe().unwrap());
+        assert_eq!(context.uid, unsafe { libc::getuid() });
+        assert_eq!(Some(context.start_time), read_start_time(std::process::id()));
+    }
+}
\ No newline at end of file
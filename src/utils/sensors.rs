@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use sysinfo::{ ComponentExt, System, SystemExt };
+
+use super::functions::{ read_number, read_trimmed };
+
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Sensor {
+    pub id: String,
+    pub chip: String,
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f64,
+    pub max: Option<f64>,
+    pub critical: Option<f64>,
+    pub history: Vec<f64>,
+}
+
+// Where readings come from; tests can point HwmonSource at a fixture directory instead of /sys.
+pub trait SensorSource {
+    fn read(&self) -> Vec<Sensor>;
+}
+
+pub struct HwmonSource {
+    pub root: PathBuf,
+}
+
+// sysinfo's components, for systems where it finds sensors hwmon alone doesn't expose.
+pub struct ComponentsSource<'a> {
+    pub system: &'a System,
+}
+
+impl SensorKind {
+    pub fn unit(&self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "°C",
+            SensorKind::Fan => "RPM",
+            SensorKind::Voltage => "V",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "Temperatura",
+            SensorKind::Fan => "Ventoinha",
+            SensorKind::Voltage => "Tensão",
+        }
+    }
+
+    // hwmon file prefix and the divisor to get to the unit above (millidegrees, RPM, millivolts).
+    fn hwmon(&self) -> (&'static str, f64) {
+        match self {
+            SensorKind::Temperature => ("temp", 1000.0),
+            SensorKind::Fan => ("fan", 1.0),
+            SensorKind::Voltage => ("in", 1000.0),
+        }
+    }
+}
+
+impl Sensor {
+    fn new(id: &str, chip: &str, label: &str, kind: SensorKind, value: f64, max: Option<f64>, critical: Option<f64>) -> Sensor {
+        Sensor {
+            // Used in alert rules, which are split on whitespace.
+            id: id.replace(char::is_whitespace, "_"),
+            chip: chip.to_string(),
+            label: label.to_string(),
+            kind,
+            value,
+            max,
+            critical,
+            history: Vec::new(),
+        }
+    }
+}
+
+impl HwmonSource {
+    pub fn default() -> HwmonSource {
+        HwmonSource { root: PathBuf::from(HWMON_ROOT) }
+    }
+}
+
+fn read_scaled(path: &Path, divisor: f64) -> Option<f64> {
+    read_number::<f64>(path).map(|value| value / divisor)
+}
+
+// Older drivers keep the attributes under device/ instead of the hwmon directory itself.
+// Ids use the hwmon directory and the attribute, since two chips (two NVMe drives, say)
+// often share both the chip name and the label.
+fn chip_sensors(dir: &Path) -> Vec<Sensor> {
+    let hwmon = dir.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let chip = read_trimmed(&dir.join("name")).or_else(|| read_trimmed(&dir.join("device").join("name"))).unwrap_or_else(|| "hwmon".to_string());
+    let mut sensors = Vec::new();
+    for attributes in [dir.to_path_buf(), dir.join("device")] {
+        let Ok(entries) = fs::read_dir(&attributes) else {
+            continue;
+        };
+        let mut inputs = entries.flatten().map(|entry| entry.file_name().to_string_lossy().to_string()).filter(|name| name.ends_with("_input")).collect::<Vec<_>>();
+        inputs.sort();
+        for input in inputs {
+            let base = input.trim_end_matches("_input");
+            let Some(kind) = [SensorKind::Temperature, SensorKind::Fan, SensorKind::Voltage].into_iter().find(|kind| {
+                base.strip_prefix(kind.hwmon().0).map(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit())).unwrap_or(false)
+            }) else {
+                continue;
+            };
+            let divisor = kind.hwmon().1;
+            let Some(value) = read_scaled(&attributes.join(&input), divisor) else {
+                continue;
+            };
+            let label = read_trimmed(&attributes.join(format!("{}_label", base))).unwrap_or_else(|| base.to_string());
+            let max = read_scaled(&attributes.join(format!("{}_max", base)), divisor);
+            let critical = read_scaled(&attributes.join(format!("{}_crit", base)), divisor);
+            sensors.push(Sensor::new(&format!("{}/{}", hwmon, base), &chip, &label, kind, value, max, critical));
+        }
+        if !sensors.is_empty() {
+            break;
+        }
+    }
+    sensors
+}
+
+impl SensorSource for HwmonSource {
+    fn read(&self) -> Vec<Sensor> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+        let mut dirs = entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>();
+        dirs.sort();
+        dirs.iter().flat_map(|dir| chip_sensors(dir)).collect()
+    }
+}
+
+impl SensorSource for ComponentsSource<'_> {
+    fn read(&self) -> Vec<Sensor> {
+        let known = |value: f32| match value.is_finite() {
+            true => Some(value as f64),
+            false => None,
+        };
+        self.system
+            .components()
+            .iter()
+            .filter_map(|component| {
+                let value = known(component.temperature())?;
+                Some(Sensor::new(&format!("sysinfo/{}", component.label()), "sysinfo", component.label(), SensorKind::Temperature, value, known(component.max()), component.critical().and_then(known)))
+            })
+            .collect()
+    }
+}
+
+// sysinfo reads hwmon too and labels its components "<chip> <label>", so those are dropped
+// when the chip was already read directly.
+pub fn read_sensors(sources: &[&dyn SensorSource]) -> Vec<Sensor> {
+    let mut sensors: Vec<Sensor> = Vec::new();
+    for source in sources {
+        for sensor in source.read() {
+            let duplicate = sensors.iter().any(|known| {
+                known.id.eq(&sensor.id) || (sensor.chip.eq("sysinfo") && sensor.label.starts_with(&format!("{} ", known.chip)))
+            });
+            if !duplicate {
+                sensors.push(sensor);
+            }
+        }
+    }
+    sensors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> HwmonSource {
+        HwmonSource { root: Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hwmon") }
+    }
+
+    #[test]
+    fn reads_every_chip_in_the_fixture() {
+        let sensors = fixture().read();
+        let ids = sensors.iter().map(|sensor| sensor.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["hwmon0/temp1", "hwmon0/temp2", "hwmon1/temp1", "hwmon2/temp1", "hwmon3/fan1", "hwmon3/in0"]);
+    }
+
+    #[test]
+    fn scales_values_and_reads_limits() {
+        let sensors = fixture().read();
+        let package = &sensors[0];
+        assert_eq!((package.chip.as_str(), package.label.as_str()), ("coretemp", "Package id 0"));
+        assert_eq!((package.value, package.max, package.critical), (45.0, Some(80.0), Some(100.0)));
+        // Without a label the attribute name stands in.
+        assert_eq!(sensors[1].label, "temp2");
+        assert_eq!(sensors[1].critical, None);
+    }
+
+    #[test]
+    fn keeps_chips_with_the_same_name_and_label_apart() {
+        let sensors = fixture().read();
+        let drives = sensors.iter().filter(|sensor| sensor.chip.eq("nvme")).collect::<Vec<_>>();
+        assert_eq!(drives.len(), 2);
+        assert!(drives.iter().all(|drive| drive.label.eq("Composite")));
+        assert_eq!((drives[0].value, drives[1].value), (35.85, 38.85));
+        assert_eq!(read_sensors(&[&fixture()]).len(), sensors.len());
+    }
+
+    #[test]
+    fn falls_back_to_device_attributes() {
+        let sensors = fixture().read();
+        let fan = sensors.iter().find(|sensor| sensor.id.eq("hwmon3/fan1")).unwrap();
+        assert!(fan.kind == SensorKind::Fan && fan.chip.eq("it87") && fan.value == 1200.0);
+        let voltage = sensors.iter().find(|sensor| sensor.id.eq("hwmon3/in0")).unwrap();
+        assert!(voltage.kind == SensorKind::Voltage && (voltage.value - 1.104).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missing_root_reads_nothing() {
+        assert!(HwmonSource { root: PathBuf::from("/nonexistent/hwmon") }.read().is_empty());
+    }
+}
\ No newline at end of file
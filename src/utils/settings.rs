@@ -0,0 +1,74 @@
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+use serde::{ Deserialize, Serialize };
+
+use super::protection::DEFAULT_PROTECTED;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub watchdog_rules: Vec<String>,
+    pub watchdog_dry_run: bool,
+    pub alert_rules: Vec<String>,
+    pub alert_desktop: bool,
+    pub protected: Vec<String>,
+    pub protection_mode: String,
+    pub read_only: bool,
+    pub cpu_chart_mode: String,
+    pub sensor_alerts: bool,
+    pub sensor_alert_margin: f64,
+    pub energy_per_process: bool,
+}
+
+impl Settings {
+    pub fn load() -> Settings {
+        Settings::load_from(&settings_path())
+    }
+
+    pub fn load_from(path: &Path) -> Settings {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = settings_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, content)
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            watchdog_rules: Vec::new(),
+            watchdog_dry_run: true,
+            alert_rules: Vec::new(),
+            alert_desktop: true,
+            protected: DEFAULT_PROTECTED.iter().map(|entry| entry.to_string()).collect(),
+            protection_mode: "block".to_string(),
+            read_only: false,
+            cpu_chart_mode: "aggregate".to_string(),
+            sensor_alerts: false,
+            sensor_alert_margin: 5.0,
+            energy_per_process: false,
+        }
+    }
+}
+
+pub fn config_dir() -> PathBuf {
+    match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("task-manager"),
+        _ => PathBuf::from(std::env::var_os("HOME").unwrap_or_default()).join(".config").join("task-manager"),
+    }
+}
+
+pub fn settings_path() -> PathBuf {
+    config_dir().join("settings.json")
+}
\ No newline at end of file
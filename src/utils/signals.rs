@@ -0,0 +1,35 @@
+use super::helper::perform;
+use super::helper_protocol::Request;
+use super::protection::Origin;
+
+pub const SIGNALS: [(&str, i32); 8] = [
+    ("SIGTERM", libc::SIGTERM),
+    ("SIGKILL", libc::SIGKILL),
+    ("SIGINT", libc::SIGINT),
+    ("SIGHUP", libc::SIGHUP),
+    ("SIGSTOP", libc::SIGSTOP),
+    ("SIGCONT", libc::SIGCONT),
+    ("SIGCHLD", libc::SIGCHLD),
+    ("SIGUSR1", libc::SIGUSR1),
+];
+
+pub fn signal_name(signal: i32) -> String {
+    SIGNALS
+        .iter()
+        .find(|(_, number)| *number == signal)
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_else(|| signal.to_string())
+}
+
+pub fn signal_number(name: &str) -> Option<i32> {
+    let name = name.trim().to_uppercase();
+    SIGNALS
+        .iter()
+        .find(|(known, _)| known.eq(&name) || known.strip_prefix("SIG").eq(&Some(name.as_str())))
+        .map(|(_, number)| *number)
+        .or_else(|| name.parse().ok())
+}
+
+pub fn send_signal(pid: u32, signal: i32, origin: Origin) -> Result<(), String> {
+    perform(Request::Signal { pid, signal }, origin).map_err(|err| format!("Erro ao enviar {} para {}: {}", signal_name(signal), pid, err))
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use std::fs;
+
+use super::app_props::MyProcess;
+
+pub fn read_state(pid: u32) -> char {
+    // The comm field may contain spaces or parens, so look after the last ')'.
+    fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|stat| stat.rsplit_once(')').and_then(|(_, rest)| rest.trim_start().chars().next()))
+        .unwrap_or('?')
+}
+
+pub fn read_wchan(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{}/wchan", pid))
+        .map(|wchan| wchan.trim().to_string())
+        .ok()
+        .filter(|wchan| !wchan.is_empty() && wchan.ne("0"))
+        .unwrap_or_else(|| "?".to_string())
+}
+
+pub fn state_label(state: char) -> &'static str {
+    match state {
+        'R' => "Executando",
+        'S' => "Dormindo",
+        'D' => "Espera de E/S (D)",
+        'Z' => "Zumbi",
+        'T' => "Parado",
+        't' => "Rastreado",
+        'I' => "Ocioso",
+        'X' => "Morto",
+        _ => "Desconhecido",
+    }
+}
+
+pub fn is_flagged(state: char) -> bool {
+    state == 'Z' || state == 'D'
+}
+
+// D-state processes grouped by the kernel function they are blocked in, most common first.
+pub fn summarize_uninterruptible(procs: &[MyProcess]) -> Vec<(String, usize)> {
+    let mut summary: Vec<(String, usize)> = Vec::new();
+    for proc in procs.iter().filter(|proc| proc.state == 'D') {
+        match summary.iter_mut().find(|(wchan, _)| wchan.eq(&proc.wchan)) {
+            Some((_, count)) => *count += 1,
+            None => summary.push((proc.wchan.clone(), 1)),
+        }
+    }
+    summary.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    summary
+}
\ No newline at end of file
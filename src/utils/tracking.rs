@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::time::{ Duration, Instant };
+
+use super::app_props::MyProcess;
+
+pub const NEW_HIGHLIGHT: Duration = Duration::from_secs(3);
+pub const EXITED_GRACE: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RowStatus {
+    New,
+    Alive,
+    Exited,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TrackedRow {
+    pub process: MyProcess,
+    pub first_seen: Instant,
+    pub exited_at: Option<Instant>,
+}
+
+impl TrackedRow {
+    pub fn status(&self, now: Instant) -> RowStatus {
+        if self.exited_at.is_some() {
+            return RowStatus::Exited;
+        }
+        if now.duration_since(self.first_seen) < NEW_HIGHLIGHT {
+            return RowStatus::New;
+        }
+        RowStatus::Alive
+    }
+
+    pub fn key(&self) -> String {
+        format!("{}-{}", self.process.pid, self.process.start_time)
+    }
+}
+
+// Keeps rows stable across snapshots, keyed by (pid, start time) so a reused PID is a new row.
+#[derive(Clone, Default, PartialEq)]
+pub struct ProcessTable {
+    rows: Vec<TrackedRow>,
+    initialized: bool,
+}
+
+impl ProcessTable {
+    pub fn update(&mut self, procs: Vec<MyProcess>, now: Instant) {
+        let mut incoming: HashMap<(u32, u64), MyProcess> = procs
+            .into_iter()
+            .map(|proc| ((proc.pid, proc.start_time), proc))
+            .collect();
+
+        self.rows.retain_mut(|row| {
+            match incoming.remove(&(row.process.pid, row.process.start_time)) {
+                Some(proc) => {
+                    row.process = proc;
+                    row.exited_at = None;
+                    true
+                }
+                None => {
+                    let exited_at = *row.exited_at.get_or_insert(now);
+                    now.duration_since(exited_at) < EXITED_GRACE
+                }
+            }
+        });
+
+        // Whatever is already running when the app starts shouldn't flash as new.
+        let first_seen = match self.initialized {
+            true => now,
+            false => now.checked_sub(NEW_HIGHLIGHT).unwrap_or(now),
+        };
+        let mut new_rows = incoming.into_values().collect::<Vec<_>>();
+        new_rows.sort_by_key(|proc| proc.pid);
+        for proc in new_rows {
+            self.rows.push(TrackedRow { process: proc, first_seen, exited_at: None });
+        }
+        self.initialized = true;
+    }
+
+    pub fn rows(&self) -> &[TrackedRow] {
+        &self.rows
+    }
+
+    pub fn live(&self) -> Vec<MyProcess> {
+        self.rows.iter().filter(|row| row.exited_at.is_none()).map(|row| row.process.clone()).collect()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+use super::app_props::MyProcess;
+
+// Breadth-first, so parents always come before their children.
+pub fn descendants(root: u32, procs: &[MyProcess]) -> Vec<u32> {
+    let mut found: Vec<u32> = Vec::new();
+    let mut idx = 0;
+    let mut current = root;
+    loop {
+        for proc in procs {
+            if proc.parent.eq(&Some(current)) && proc.pid.ne(&root) && !found.contains(&proc.pid) {
+                found.push(proc.pid);
+            }
+        }
+        if idx >= found.len() {
+            return found;
+        }
+        current = found[idx];
+        idx += 1;
+    }
+}
\ No newline at end of file
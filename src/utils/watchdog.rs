@@ -0,0 +1,312 @@
+use std::collections::{ HashMap, HashSet };
+
+use regex::Regex;
+
+use super::app_props::MyProcess;
+use super::audit::{ audited, AuditTarget };
+use super::priority::renice;
+use super::protection::{ Guard, Origin };
+use super::signals::{ send_signal, signal_name, signal_number };
+
+pub const MAX_WATCHDOG_LOG: usize = 1000;
+
+#[derive(Clone)]
+pub enum Condition {
+    NameMatches(Regex),
+    CmdMatches(Regex),
+    NameEquals(String),
+    Memory(Comparison, u64),
+    Cpu(Comparison, f32),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Signal(i32),
+    Renice(i32),
+}
+
+#[derive(Clone)]
+pub struct Rule {
+    pub text: String,
+    pub conditions: Vec<Condition>,
+    pub sustain: u64,
+    pub action: Action,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct WatchdogEntry {
+    pub timestamp: u64,
+    pub rule: String,
+    pub pid: u32,
+    pub name: String,
+    pub action: String,
+    pub dry_run: bool,
+    pub outcome: String,
+}
+
+impl Comparison {
+    fn holds<T: PartialOrd>(&self, value: T, threshold: T) -> bool {
+        match self {
+            Comparison::Greater => value > threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Less => value < threshold,
+            Comparison::LessOrEqual => value <= threshold,
+        }
+    }
+}
+
+impl Condition {
+    fn matches(&self, proc: &MyProcess) -> bool {
+        match self {
+            Condition::NameMatches(regex) => regex.is_match(&proc.name),
+            Condition::CmdMatches(regex) => regex.is_match(&proc.cmd),
+            Condition::NameEquals(name) => proc.name.eq(name),
+            // MyProcess::memory is in MB (10^6 bytes).
+            Condition::Memory(comparison, bytes) => comparison.holds(proc.memory * 1000000, *bytes),
+            Condition::Cpu(comparison, percent) => comparison.holds(proc.cpu_usage, *percent),
+        }
+    }
+}
+
+impl Action {
+    pub fn describe(&self) -> String {
+        match self {
+            Action::Signal(signal) => signal_name(*signal),
+            Action::Renice(nice) => format!("renice {}", nice),
+        }
+    }
+
+    fn apply(&self, pid: u32) -> Result<(), String> {
+        match self {
+            Action::Signal(signal) => send_signal(pid, *signal, Origin::Automatic),
+            Action::Renice(nice) => renice(pid, *nice, Origin::Automatic),
+        }
+    }
+}
+
+fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.replace(' ', "");
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number = number.parse::<f64>().map_err(|_| format!("tamanho inválido: {}", value))?;
+    let multiplier = match unit.to_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
+        _ => return Err(format!("unidade inválida: {}", unit)),
+    };
+    Ok((number * multiplier) as u64)
+}
+
+pub fn parse_duration(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (number, multiplier) = match value.chars().last() {
+        Some('s') => (&value[..value.len() - 1], 1),
+        Some('m') => (&value[..value.len() - 1], 60),
+        Some('h') => (&value[..value.len() - 1], 3600),
+        _ => (value, 1),
+    };
+    number.trim().parse::<u64>().map(|secs| secs * multiplier).map_err(|_| format!("duração inválida: {}", value))
+}
+
+fn parse_condition(text: &str) -> Result<Condition, String> {
+    let operators = [("=~", None), ("==", None), (">=", Some(Comparison::GreaterOrEqual)), ("<=", Some(Comparison::LessOrEqual)), (">", Some(Comparison::Greater)), ("<", Some(Comparison::Less))];
+    let (operator, comparison, at) = operators
+        .iter()
+        .find_map(|(operator, comparison)| text.find(operator).map(|at| (*operator, *comparison, at)))
+        .ok_or_else(|| format!("condição sem operador: {}", text))?;
+    let field = text[..at].trim();
+    let value = text[at + operator.len()..].trim();
+    match (field, operator, comparison) {
+        ("name", "=~", _) => Regex::new(value).map(Condition::NameMatches).map_err(|err| format!("regex inválida: {}", err)),
+        ("cmd", "=~", _) => Regex::new(value).map(Condition::CmdMatches).map_err(|err| format!("regex inválida: {}", err)),
+        ("name", "==", _) => Ok(Condition::NameEquals(value.to_string())),
+        ("memory", _, Some(comparison)) => Ok(Condition::Memory(comparison, parse_size(value)?)),
+        ("cpu", _, Some(comparison)) => value.trim_end_matches('%').trim().parse::<f32>()
+            .map(|percent| Condition::Cpu(comparison, percent))
+            .map_err(|_| format!("CPU inválida: {}", value)),
+        _ => Err(format!("condição não suportada: {}", text)),
+    }
+}
+
+fn parse_action(text: &str) -> Result<Action, String> {
+    let words = text.split_whitespace().collect::<Vec<_>>();
+    match words.as_slice() {
+        ["renice", nice] | ["renice", "to", nice] => nice.parse().map(Action::Renice).map_err(|_| format!("nice inválido: {}", nice)),
+        ["kill"] => Ok(Action::Signal(libc::SIGTERM)),
+        [signal] | ["kill", signal] => signal_number(signal).map(Action::Signal).ok_or_else(|| format!("sinal desconhecido: {}", signal)),
+        _ => Err(format!("ação não suportada: {}", text)),
+    }
+}
+
+// Accepts "if <cond> [and <cond>]... [for <duration>] then <action>"
+// and the shorthand "renice any <regex> to <nice>".
+pub fn parse_rule(text: &str) -> Result<Rule, String> {
+    let line = text.trim();
+    if let Some(rest) = line.strip_prefix("renice any ") {
+        let (pattern, nice) = rest.rsplit_once(" to ").ok_or("esperado: renice any <nome> to <nice>")?;
+        return Ok(Rule {
+            text: line.to_string(),
+            conditions: vec![parse_condition(&format!("name=~{}", pattern.trim()))?],
+            sustain: 0,
+            action: parse_action(&format!("renice {}", nice.trim()))?,
+        });
+    }
+    let body = line.strip_prefix("if ").ok_or("regras começam com \"if\" ou \"renice any\"")?;
+    let (conditions, action) = body.rsplit_once(" then ").ok_or("faltou \"then <ação>\"")?;
+    let (conditions, sustain) = match conditions.rsplit_once(" for ") {
+        Some((conditions, duration)) => (conditions, parse_duration(duration)?),
+        None => (conditions, 0),
+    };
+    Ok(Rule {
+        text: line.to_string(),
+        conditions: conditions.split(" and ").map(parse_condition).collect::<Result<Vec<_>, _>>()?,
+        sustain,
+        action: parse_action(action)?,
+    })
+}
+
+pub fn parse_rules(lines: &[String]) -> (Vec<Rule>, Vec<String>) {
+    let mut rules = Vec::new();
+    let mut errors = Vec::new();
+    for line in lines.iter().filter(|line| !line.trim().is_empty() && !line.trim().starts_with('#')) {
+        match parse_rule(line) {
+            Ok(rule) => rules.push(rule),
+            Err(err) => errors.push(format!("{}: {}", line.trim(), err)),
+        }
+    }
+    (rules, errors)
+}
+
+// Remembers since when each (rule, process) pair has matched, and which already fired.
+#[derive(Default)]
+pub struct Watchdog {
+    matching_since: HashMap<(String, u32, u64), u64>,
+    fired: HashSet<(String, u32, u64)>,
+}
+
+impl Watchdog {
+    pub fn evaluate(&mut self, rules: &[Rule], procs: &[MyProcess], dry_run: bool, guard: &Guard, now: u64) -> Vec<WatchdogEntry> {
+        let mut entries = Vec::new();
+        let mut still_matching = HashSet::new();
+        for rule in rules {
+            for proc in procs.iter().filter(|proc| proc.pid != std::process::id()) {
+                if !rule.conditions.iter().all(|condition| condition.matches(proc)) {
+                    continue;
+                }
+                let key = (rule.text.clone(), proc.pid, proc.start_time);
+                still_matching.insert(key.clone());
+                let since = *self.matching_since.entry(key.clone()).or_insert(now);
+                if now.saturating_sub(since) < rule.sustain || self.fired.contains(&key) {
+                    continue;
+                }
+                self.fired.insert(key);
+                let action = format!("vigia: {}", rule.action.describe());
+                // Nobody is around to confirm, so protected processes are always left alone.
+                let outcome = match (dry_run, guard.check(proc.pid).refusal()) {
+                    (true, _) => "simulado".to_string(),
+                    (false, Some(reason)) => {
+                        let outcome = format!("recusado: {}", reason);
+                        AuditTarget::capture(proc.pid).record(&action, &rule.text, &outcome);
+                        outcome
+                    }
+                    (false, None) => match audited(proc.pid, &action, &rule.text, || rule.action.apply(proc.pid)) {
+                        Ok(()) => "ok".to_string(),
+                        Err(err) => err,
+                    },
+                };
+                entries.push(WatchdogEntry {
+                    timestamp: now,
+                    rule: rule.text.clone(),
+                    pid: proc.pid,
+                    name: proc.name.clone(),
+                    action: rule.action.describe(),
+                    dry_run,
+                    outcome,
+                });
+            }
+        }
+        self.matching_since.retain(|key, _| still_matching.contains(key));
+        self.fired.retain(|key| still_matching.contains(key));
+        entries
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::namespaces::Namespaces;
+
+    fn proc(name: &str, cmd: &str, memory: u64, cpu_usage: f32) -> MyProcess {
+        MyProcess {
+            pid: 42,
+            name: name.to_string(),
+            memory,
+            cpu_usage,
+            read_bytes: 0,
+            written_bytes: 0,
+            namespaces: Namespaces::default(),
+            parent: Some(1),
+            cgroup: String::new(),
+            frozen: false,
+            oom_score: 0,
+            oom_score_adj: 0,
+            start_time: 100,
+            cmd: cmd.to_string(),
+            state: 'S',
+            wchan: String::new(),
+        }
+    }
+
+    fn matches(rule: &str, proc: &MyProcess) -> bool {
+        parse_rule(rule).unwrap().conditions.iter().all(|condition| condition.matches(proc))
+    }
+
+    #[test]
+    fn parses_full_rules() {
+        let rule = parse_rule("if name=~chrome and memory > 4 GiB for 30s then SIGKILL").unwrap();
+        assert_eq!((rule.conditions.len(), rule.sustain), (2, 30));
+        assert!(rule.action == Action::Signal(libc::SIGKILL));
+        let rule = parse_rule("renice any cc1plus to 19").unwrap();
+        assert!(rule.action == Action::Renice(19) && rule.sustain == 0);
+    }
+
+    #[test]
+    fn name_only_looks_at_the_process_name() {
+        let renderer = proc("chrome", "/opt/google/chrome/chrome --type=renderer", 0, 0.0);
+        let editor = proc("vim", "vim notes-about-chrome.txt", 0, 0.0);
+        assert!(matches("if name=~chrom then kill", &renderer));
+        assert!(!matches("if name=~chrom then kill", &editor));
+        assert!(matches("if cmd=~--type=renderer then kill", &renderer));
+        assert!(!matches("if cmd=~--type=renderer then kill", &editor));
+    }
+
+    #[test]
+    fn compares_memory_and_cpu() {
+        // memory is in MB.
+        let busy = proc("worker", "worker", 5000, 95.0);
+        assert!(matches("if memory > 4 GiB and cpu >= 95% then kill", &busy));
+        assert!(!matches("if memory < 4 GiB then kill", &busy));
+        assert!(!matches("if cpu > 95 then kill", &busy));
+    }
+
+    #[test]
+    fn reports_bad_rules() {
+        let lines = ["if name=~( then kill", "if name==x then reboot", "name==x then kill", "if cpu > 9 for 5x then kill", "# comentário", "if name==x then kill"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let (rules, errors) = parse_rules(&lines);
+        assert_eq!((rules.len(), errors.len()), (1, 4));
+    }
+}
\ No newline at end of file